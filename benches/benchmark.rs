@@ -1,64 +1,145 @@
-use aoc2022::*;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use aoc2022::formats;
+use aoc2022::profile::Profile;
+use aoc2022::registry::{self, Solution, Visitor};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 
-macro_rules! input {
-    ($day:ident) => {
-        let $day =
-            std::fs::read_to_string(concat!("data/github/inputs/", stringify!($day), ".txt")).unwrap();
-    };
+/// Whether to rotate through the inputs of all profiles, using the next one on each iteration,
+/// instead of benchmarking each profile on its own. Set with `AOC_BENCH_CYCLE=1`.
+fn cycle() -> bool {
+    std::env::var("AOC_BENCH_CYCLE").is_ok_and(|value| value != "0")
 }
 
-macro_rules! run {
-    ( $module:ident, $day:ident) => {
-        $module::$day::run(black_box($day.as_str()))
-    };
+/// The input of a day in a profile, if there is one the implementation supports.
+fn input<S: Solution>(profile: &Profile) -> Option<String> {
+    let input = profile.input(S::DAY).ok()?;
+    formats::check(S::DAY, &input).ok()?;
+    S::validate(&input).is_empty().then_some(input)
 }
 
-macro_rules! bench {
-    ( $c:ident, $module:ident, $day:ident) => {
-        $c.bench_function(stringify!($day), |b| b.iter(|| run!($module, $day)));
-    };
+/// An implementation of a day, with its answers dropped
+/// so that the implementations of all days fit in one list.
+struct Implementation {
+    day: usize,
+    /// Module of the implementation, like `optimized`.
+    module: &'static str,
+    run: fn(&str),
+    input: fn(&Profile) -> Option<String>,
 }
 
-macro_rules! run_benchmarks {
-    ( $c:ident, $(($module:ident, $day:ident)),+ ) => {
-        $( input!($day); )+
-        $( bench!($c, $module, $day); )+
-        $c.bench_function("all", |b| {b.iter(|| {
-            $( run!($module, $day); )+
-        })});
-    };
+struct Implementations(Vec<Implementation>);
+
+impl Visitor for Implementations {
+    fn visit<S: Solution>(&mut self) {
+        let (module, _) = S::NAME.split_once("::").unwrap();
+        self.0.push(Implementation {
+            day: S::DAY,
+            module,
+            run: |input| {
+                black_box(S::run(input));
+            },
+            input: input::<S>,
+        });
+    }
+}
+
+/// Benchmarks each day in a group,
+/// with a benchmark for each implementation and profile, like `day13/optimized/github`.
+fn bench_days(c: &mut Criterion, implementations: &[Implementation], profiles: &[Profile]) {
+    for day in implementations.chunk_by(|a, b| a.day == b.day) {
+        let inputs: Vec<Vec<(&str, String)>> = day
+            .iter()
+            .map(|implementation| {
+                profiles
+                    .iter()
+                    .filter_map(|profile| {
+                        Some((profile.name.as_str(), (implementation.input)(profile)?))
+                    })
+                    .collect()
+            })
+            .collect();
+        if inputs.iter().all(Vec::is_empty) {
+            continue;
+        }
+        let mut group = c.benchmark_group(format!("day{:02}", day[0].day));
+        for (implementation, inputs) in day.iter().zip(&inputs) {
+            let run = implementation.run;
+            if cycle() {
+                if inputs.is_empty() {
+                    continue;
+                }
+                let mut inputs = inputs.iter().map(|(_, input)| input.as_str()).cycle();
+                let id = BenchmarkId::new(implementation.module, "cycle");
+                group.bench_function(id, |b| b.iter(|| run(black_box(inputs.next().unwrap()))));
+            } else {
+                for (name, input) in inputs {
+                    let id = BenchmarkId::new(implementation.module, name);
+                    group.bench_with_input(id, input, |b, input| {
+                        b.iter(|| run(black_box(input.as_str())))
+                    });
+                }
+            }
+        }
+        group.finish();
+    }
+}
+
+/// An input, with the implementation of its day that runs on it.
+type Run = (fn(&str), String);
+
+/// The input of every day in a profile, each with the implementation the runner uses for it:
+/// the first one that supports the input.
+/// Fails with the first day whose input is missing or not supported by any implementation.
+fn all_inputs(implementations: &[Implementation], profile: &Profile) -> Result<Vec<Run>, usize> {
+    implementations
+        .chunk_by(|a, b| a.day == b.day)
+        .map(|day| {
+            day.iter()
+                .find_map(|implementation| {
+                    Some((implementation.run, (implementation.input)(profile)?))
+                })
+                .ok_or(day[0].day)
+        })
+        .collect()
+}
+
+/// Runs every day in sequence, given inputs loaded in advance.
+fn run_all(inputs: &[Run]) {
+    for (run, input) in inputs {
+        run(black_box(input.as_str()));
+    }
 }
 
 pub fn criterion_benchmark(c: &mut Criterion) {
-    run_benchmarks!(
-        c,
-        (optimized, day01),
-        (solutions, day02),
-        (solutions, day03),
-        (solutions, day04),
-        (solutions, day05),
-        (optimized, day06),
-        (solutions, day07),
-        (solutions, day08),
-        (solutions, day09),
-        (solutions, day10),
-        (optimized, day11),
-        (solutions, day12),
-        (optimized, day13),
-        (optimized, day14),
-        (optimized, day15),
-        (optimized, day16),
-        (solutions, day17),
-        (solutions, day18),
-        (optimized, day19),
-        (solutions, day20),
-        (solutions, day21),
-        (solutions, day22),
-        (optimized, day23),
-        (optimized, day24),
-        (solutions, day25)
-    );
+    let profiles = Profile::all().expect("no data directory");
+    let mut implementations = Implementations(Vec::new());
+    registry::visit_all(&mut implementations);
+    bench_days(c, &implementations.0, &profiles);
+
+    let complete: Vec<(&str, Vec<Run>)> = profiles
+        .iter()
+        .filter_map(|profile| match all_inputs(&implementations.0, profile) {
+            Ok(inputs) => Some((profile.name.as_str(), inputs)),
+            Err(day) => {
+                eprintln!("Leaving profile {} out of `all`: no input for day {day}", profile.name);
+                None
+            }
+        })
+        .collect();
+    if complete.is_empty() {
+        return;
+    }
+    let mut group = c.benchmark_group("all");
+    if cycle() {
+        let mut inputs = complete.iter().map(|(_, inputs)| inputs).cycle();
+        group.bench_function("cycle", |b| b.iter(|| run_all(inputs.next().unwrap())));
+    } else {
+        for (name, inputs) in &complete {
+            group.bench_with_input(BenchmarkId::from_parameter(name), inputs, |b, inputs| {
+                b.iter(|| run_all(inputs))
+            });
+        }
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
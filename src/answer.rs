@@ -0,0 +1,259 @@
+//! A common type for the answers of all days.
+//!
+//! Solutions return whatever type is natural for them,
+//! which is converted into an [`Answer`] with `From` for comparing, printing and serialising.
+
+use std::fmt::{Display, Write};
+
+use crate::ocr::{self, OcrError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    /// A picture that has to be read, like the CRT of day 10.
+    Bitmap(Bitmap),
+    /// No answer, like part 2 of day 25.
+    None,
+}
+
+/// Pixels in rows of equal width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    const ON: char = '█';
+    const OFF: char = '░';
+
+    /// A bitmap from pixels in row-major order.
+    pub fn new(width: usize, pixels: Vec<bool>) -> Self {
+        assert!(width > 0 && pixels.len().is_multiple_of(width), "pixels don't fill the rows");
+        Self { width, pixels }
+    }
+
+    /// Parse a bitmap drawn with `█` or `#` for pixels that are on, and `░`, `.` or spaces for
+    /// pixels that are off. Short rows are padded with pixels that are off.
+    pub fn parse(s: &str) -> Option<Self> {
+        let rows: Vec<&str> = s.trim_end().lines().map(str::trim_end).collect();
+        let width = rows.iter().map(|row| row.chars().count()).max().filter(|&w| w > 0)?;
+        let mut pixels = Vec::with_capacity(width * rows.len());
+        for row in rows {
+            let len = pixels.len();
+            for c in row.chars() {
+                pixels.push(match c {
+                    Self::ON | '#' => true,
+                    Self::OFF | '.' | ' ' => false,
+                    _ => return None,
+                });
+            }
+            pixels.resize(len + width, false);
+        }
+        Some(Self { width, pixels })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && self.pixels.get(x + y * self.width).copied().unwrap_or(false)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.width)
+    }
+}
+
+/// Draws the rows on separate lines, without a trailing newline.
+impl Display for Bitmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            for &pixel in row {
+                f.write_char(if pixel { Self::ON } else { Self::OFF })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Answer {
+    /// Whether the answers of two implementations to the same part agree.
+    /// An implementation that leaves the part out, answering [`Answer::None`],
+    /// agrees with any answer.
+    pub fn agrees(&self, other: &Answer) -> bool {
+        self == other || *self == Answer::None || *other == Answer::None
+    }
+
+    /// The answer as it would be typed in, reading the letters drawn by bitmaps.
+    pub fn text(&self) -> Result<String, OcrError> {
+        match self {
+            Answer::Bitmap(bitmap) => ocr::read(bitmap),
+            answer => Ok(answer.to_string()),
+        }
+    }
+
+    /// The answer as JSON: a number, a string, an array of rows of a bitmap, or `null`.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Int(n) => n.to_string(),
+            Answer::Str(s) => json_string(s),
+            Answer::Bitmap(bitmap) => {
+                let rows: Vec<String> = bitmap.to_string().lines().map(json_string).collect();
+                format!("[{}]", rows.join(", "))
+            }
+            Answer::None => String::from("null"),
+        }
+    }
+}
+
+/// Formats the answer as it is written in solution files.
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => f.write_str(s),
+            Answer::Bitmap(bitmap) => write!(f, "{bitmap}"),
+            Answer::None => Ok(()),
+        }
+    }
+}
+
+/// Compares with an answer from a solution file, ignoring trailing whitespace.
+/// Bitmaps match either the same picture or the letters they draw.
+impl PartialEq<str> for Answer {
+    fn eq(&self, solution: &str) -> bool {
+        match self {
+            Answer::Int(n) => solution.trim().parse() == Ok(*n),
+            Answer::Str(s) => s.trim_end() == solution.trim_end(),
+            Answer::Bitmap(bitmap) => match Bitmap::parse(solution) {
+                Some(picture) => picture == *bitmap,
+                None => ocr::read(bitmap).is_ok_and(|text| text == solution.trim()),
+            },
+            Answer::None => solution.trim().is_empty(),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n as i128)
+                }
+            }
+        )+
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_owned())
+    }
+}
+
+impl From<Bitmap> for Answer {
+    fn from(bitmap: Bitmap) -> Self {
+        Answer::Bitmap(bitmap)
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::None
+    }
+}
+
+/// A JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(42u16), Answer::Int(42));
+        assert_eq!(Answer::from(-7i64), Answer::Int(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::Int(u64::MAX as i128));
+        assert_eq!(Answer::from("CMZ"), Answer::Str(String::from("CMZ")));
+        assert_eq!(Answer::from(()), Answer::None);
+        assert_eq!(Answer::from(()).to_string(), "");
+    }
+
+    #[test]
+    fn agreement() {
+        assert!(Answer::Int(54).agrees(&Answer::Int(54)));
+        assert!(!Answer::Int(54).agrees(&Answer::Int(0)));
+        assert!(Answer::Int(54).agrees(&Answer::None));
+        assert!(Answer::None.agrees(&Answer::Str(String::from("2=-1=0"))));
+    }
+
+    #[test]
+    fn solution_files() {
+        assert!(Answer::from(1651u16) == *"1651\n");
+        assert!(Answer::from(1651u16) != *"1652");
+        assert!(Answer::Str(String::from("2=-1=0")) == *"2=-1=0\n");
+        assert!(Answer::None == *"");
+        assert!(Answer::None != *"0");
+
+        let bitmap = Bitmap::new(3, vec![true, false, true, false, true, false]);
+        assert_eq!(bitmap.to_string(), "█░█\n░█░");
+        let answer = Answer::from(bitmap.clone());
+        assert!(answer == *"█░█\n░█░\n");
+        assert!(answer == *"#.#\n.#");
+        assert!(answer != *"#.#\n##.");
+        assert_eq!(Bitmap::parse("#.#\n.#"), Some(bitmap));
+        assert_eq!(Bitmap::parse("#x"), None);
+
+        let letter = Bitmap::parse("#..#\n#..#\n####\n#..#\n#..#\n#..#").unwrap();
+        let answer = Answer::from(letter);
+        assert!(answer == *"H\n");
+        assert!(answer != *"A");
+        assert_eq!(answer.text(), Ok(String::from("H")));
+        assert_eq!(Answer::from(12u8).text(), Ok(String::from("12")));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(Answer::from(12u32).to_json(), "12");
+        assert_eq!(Answer::from("a\"b\nc").to_json(), r#""a\"b\nc""#);
+        assert_eq!(Answer::None.to_json(), "null");
+        let bitmap = Bitmap::new(2, vec![true, false, false, true]);
+        assert_eq!(Answer::from(bitmap).to_json(), r#"["█░", "░█"]"#);
+    }
+}
@@ -0,0 +1,369 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use aoc2022::params::Override;
+
+use crate::output::Format;
+use crate::snapshot::parse_threshold;
+use crate::timing::{parse_duration, Repeat};
+
+pub const USAGE: &str = "\
+Usage: aoc2022 [COMMAND] [OPTIONS]
+
+Commands:
+    run                 Run the solutions (default)
+    fetch               Download the missing inputs of the selected days and profiles
+    submit [ANSWER]     Submit the answer to the selected day and part for the selected profile,
+                        running the solution if no answer is given
+    watch               Rebuild and re-run the selected day whenever its sources or inputs change,
+                        showing how the answers and times changed
+    new-day <DAY>       Add a stub implementation of a day to `src/solutions/`, or to
+                        `src/optimized/` with `--optimized`, and register it after the
+                        existing ones, or in front of them with `--first`
+    generate [SEED]     Write random inputs for the selected days to the profile `seed-<SEED>`,
+                        using seed 0 if none is given
+    fuzz [RUNS]         Compare every implementation of the selected days on RUNS generated
+                        inputs (default 1000), saving shrunk inputs they disagree, panic or get
+                        stuck on to `data/`
+
+Options:
+    --day <DAYS>        Only run the given days, e.g. `16`, `10-15` or `1,3,20-25`
+    --days <DAYS>       Same as `--day`
+    --part <PART>       Only show answers for part 1 or 2
+    --profile <NAME>    Only run the given profile, may be repeated
+    --param <DAY.NAME=VALUE>
+                        Override a parameter from the puzzle text, e.g. `day15.row=10`
+                        to run the example of day 15, may be repeated
+    --cross-check       Run every implementation of each day and report disagreements
+    --parallel          Run days and profiles on worker threads, making timings unreliable
+    --jobs <N>          Same as `--parallel`, with N worker threads
+    --format <FORMAT>   Output as `table` (default), `json`, `csv` or `markdown`
+    --repeat <N>        Run each solution at least N times and report timing statistics
+    --min-time <TIME>   Run each solution for at least TIME, e.g. `2s` or `500ms`
+    --save <FILE>       Save the times of this run as a snapshot
+    --compare <FILE>    Compare times against a snapshot saved with `--save`
+    --threshold <PCT>   Flag days that got slower than the snapshot by more than PCT percent
+                        (default 10)
+    --base-url <URL>    Server for `fetch` and `submit`
+                        (default `AOC_BASE_URL` or `https://adventofcode.com`)
+    --throttle <TIME>   Wait at least TIME between requests to the server (default 3s)
+    --interval <TIME>   How often `watch` checks for changes (default 500ms)
+    --optimized         Add the new day to `src/optimized/`
+    --first             Make the new day the chosen implementation of its day
+    -h, --help          Print this message";
+
+pub const DAYS: RangeInclusive<usize> = 1..=25;
+
+/// What the runner should do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Fetch,
+    /// Submit the given answer, or the answer of the solution.
+    Submit(Option<String>),
+    Watch,
+    /// Add a stub implementation of a day.
+    NewDay(usize),
+    /// Generate inputs from the given seed.
+    Generate(Option<u64>),
+    /// Compare the implementations on the given number of generated inputs.
+    Fuzz(Option<u64>),
+}
+
+/// Selection of days, parts and profiles to run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    days: [bool; 25],
+    parts: [bool; 2],
+    profiles: Vec<String>,
+    /// Overrides of the parameters of the days.
+    pub params: Vec<Override>,
+    pub cross_check: bool,
+    /// Number of worker threads when running in parallel.
+    pub jobs: Option<usize>,
+    pub format: Format,
+    pub repeat: Repeat,
+    pub save: Option<String>,
+    pub compare: Option<String>,
+    /// Slowdown in percent above which a day counts as a regression.
+    pub threshold: f64,
+    pub base_url: Option<String>,
+    /// Minimum time between requests to the server.
+    pub throttle: Duration,
+    /// Time between checks for changes when watching.
+    pub interval: Duration,
+    /// Whether `new-day` adds an optimized implementation.
+    pub optimized: bool,
+    /// Whether `new-day` registers the implementation in front of the existing ones.
+    pub first: bool,
+    pub help: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            command: Command::Run,
+            days: [true; 25],
+            parts: [true; 2],
+            profiles: Vec::new(),
+            params: Vec::new(),
+            cross_check: false,
+            jobs: None,
+            format: Format::Table,
+            repeat: Repeat::default(),
+            save: None,
+            compare: None,
+            threshold: 10.0,
+            base_url: None,
+            throttle: Duration::from_secs(3),
+            interval: Duration::from_millis(500),
+            optimized: false,
+            first: false,
+            help: false,
+        }
+    }
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut result = Self::default();
+        let mut days: Option<[bool; 25]> = None;
+        let mut command = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Allow both `--flag value` and `--flag=value`.
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => {
+                    (flag.to_owned(), Some(value.to_owned()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for `{flag}`"))
+            };
+            match flag.as_str() {
+                "--day" | "--days" => {
+                    let days = days.get_or_insert([false; 25]);
+                    for day in parse_days(&value()?)? {
+                        days[day - 1] = true;
+                    }
+                }
+                "--part" => {
+                    result.parts = match value()?.as_str() {
+                        "1" => [true, false],
+                        "2" => [false, true],
+                        part => return Err(format!("invalid part `{part}`")),
+                    }
+                }
+                "--profile" => result.profiles.push(value()?),
+                "--param" => result.params.push(Override::parse(&value()?)?),
+                "--cross-check" => result.cross_check = true,
+                "--parallel" => {
+                    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+                    result.jobs.get_or_insert(threads);
+                }
+                "--jobs" => {
+                    result.jobs = match value()?.parse() {
+                        Ok(jobs) if jobs > 0 => Some(jobs),
+                        _ => return Err(String::from("`--jobs` expects a positive integer")),
+                    }
+                }
+                "--format" => result.format = Format::parse(&value()?)?,
+                "--repeat" => {
+                    result.repeat.runs = match value()?.parse() {
+                        Ok(runs) if runs > 0 => runs,
+                        _ => return Err(String::from("`--repeat` expects a positive integer")),
+                    }
+                }
+                "--min-time" => result.repeat.min_time = parse_duration(&value()?)?,
+                "--save" => result.save = Some(value()?),
+                "--compare" => result.compare = Some(value()?),
+                "--threshold" => result.threshold = parse_threshold(&value()?)?,
+                "--base-url" => result.base_url = Some(value()?),
+                "--throttle" => result.throttle = parse_duration(&value()?)?,
+                "--interval" => result.interval = parse_duration(&value()?)?,
+                "--optimized" => result.optimized = true,
+                "--first" => result.first = true,
+                "-h" | "--help" => result.help = true,
+                _ if flag.starts_with('-') => return Err(format!("unknown argument `{flag}`")),
+                // Positional arguments: the command, and the answer of `submit`,
+                // the day of `new-day`, the seed of `generate` or the runs of `fuzz`
+                _ => match (&mut command, flag.as_str()) {
+                    (None, "run") => command = Some(Command::Run),
+                    (None, "fetch") => command = Some(Command::Fetch),
+                    (None, "submit") => command = Some(Command::Submit(None)),
+                    (None, "watch") => command = Some(Command::Watch),
+                    (None, "new-day") => command = Some(Command::NewDay(0)),
+                    (None, "generate") => command = Some(Command::Generate(None)),
+                    (None, "fuzz") => command = Some(Command::Fuzz(None)),
+                    (None, _) => return Err(format!("unknown command `{flag}`")),
+                    (Some(Command::Submit(answer @ None)), _) => *answer = Some(flag.clone()),
+                    (Some(Command::NewDay(day @ 0)), _) => match flag.parse() {
+                        Ok(n) if DAYS.contains(&n) => *day = n,
+                        _ => return Err(format!("invalid day `{flag}`")),
+                    },
+                    (Some(Command::Generate(seed @ None)), _) => match flag.parse() {
+                        Ok(n) => *seed = Some(n),
+                        _ => return Err(format!("invalid seed `{flag}`")),
+                    },
+                    (Some(Command::Fuzz(runs @ None)), _) => match flag.parse() {
+                        Ok(n) => *runs = Some(n),
+                        _ => return Err(format!("invalid number of runs `{flag}`")),
+                    },
+                    (Some(_), _) => return Err(format!("unexpected argument `{flag}`")),
+                },
+            }
+        }
+        if let Some(days) = days {
+            result.days = days;
+        }
+        result.command = command.unwrap_or(Command::Run);
+        if result.command == Command::NewDay(0) {
+            return Err(String::from("`new-day` expects a day"));
+        }
+        if result.jobs.is_some() && result.save.is_some() {
+            return Err(String::from("times measured in parallel can't be saved as a snapshot"));
+        }
+        Ok(result)
+    }
+
+    pub fn day(&self, day: usize) -> bool {
+        DAYS.contains(&day) && self.days[day - 1]
+    }
+
+    /// Part selection as `[part 1, part 2]`.
+    pub fn parts(&self) -> [bool; 2] {
+        self.parts
+    }
+
+    pub fn profile(&self, profile: &str) -> bool {
+        self.profiles.is_empty() || self.profiles.iter().any(|p| p == profile)
+    }
+
+    pub fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+}
+
+/// Parse a comma-separated list of days and inclusive day ranges.
+fn parse_days(list: &str) -> Result<Vec<usize>, String> {
+    let parse_day = |s: &str| -> Result<usize, String> {
+        match s.trim().parse::<usize>() {
+            Ok(day) if DAYS.contains(&day) => Ok(day),
+            _ => Err(format!("invalid day `{s}`")),
+        }
+    };
+    let mut result = Vec::new();
+    for item in list.split(',') {
+        if let Some((start, end)) = item.split_once('-') {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if start > end {
+                return Err(format!("invalid day range `{item}`"));
+            }
+            result.extend(start..=end);
+        } else {
+            result.push(parse_day(item)?);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        let args = parse(&[]).unwrap();
+        assert!((1..=25).all(|day| args.day(day)));
+        assert_eq!(args.parts(), [true, true]);
+        assert!(args.profile("github"));
+    }
+
+    #[test]
+    fn days() {
+        let args = parse(&["--days", "10-12", "--day=16", "--day", "1,3"]).unwrap();
+        let days: Vec<usize> = (1..=25).filter(|&day| args.day(day)).collect();
+        assert_eq!(days, vec![1, 3, 10, 11, 12, 16]);
+    }
+
+    #[test]
+    fn parts_and_profiles() {
+        let args = parse(&["--part", "2", "--profile", "a", "--profile=b"]).unwrap();
+        assert_eq!(args.parts(), [false, true]);
+        assert!(args.profile("a") && args.profile("b") && !args.profile("c"));
+        let args = parse(&["--param", "day15.row=10", "--param=day22.cube_size=4"]).unwrap();
+        let params: Vec<_> = args.params.iter().map(|o| (o.day, o.name.as_str())).collect();
+        assert_eq!(params, [(15, "row"), (22, "cube_size")]);
+        assert!(parse(&["--param", "row=10"]).is_err());
+    }
+
+    #[test]
+    fn parallel() {
+        assert_eq!(parse(&[]).unwrap().jobs, None);
+        assert!(parse(&["--parallel"]).unwrap().jobs.is_some_and(|jobs| jobs > 0));
+        assert_eq!(parse(&["--jobs", "3", "--parallel"]).unwrap().jobs, Some(3));
+        assert!(parse(&["--jobs", "0"]).is_err());
+        assert!(parse(&["--parallel", "--save", "times.tsv"]).is_err());
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse(&[]).unwrap().command, Command::Run);
+        let args = parse(&["fetch", "--profile", "a", "--base-url=http://localhost:8000"]).unwrap();
+        assert_eq!(args.command, Command::Fetch);
+        assert_eq!(args.base_url.as_deref(), Some("http://localhost:8000"));
+        let args = parse(&["submit", "--day", "25", "2=-1=0", "--throttle", "10s"]).unwrap();
+        assert_eq!(args.command, Command::Submit(Some(String::from("2=-1=0"))));
+        assert_eq!(args.throttle, Duration::from_secs(10));
+        assert_eq!(parse(&["submit"]).unwrap().command, Command::Submit(None));
+        assert!(parse(&["frobnicate"]).is_err());
+        assert!(parse(&["fetch", "extra"]).is_err());
+        assert!(parse(&["submit", "1", "2"]).is_err());
+        let args = parse(&["watch", "--day", "16", "--interval=2s"]).unwrap();
+        assert_eq!(args.command, Command::Watch);
+        assert_eq!(args.interval, Duration::from_secs(2));
+        let args = parse(&["new-day", "17", "--optimized"]).unwrap();
+        assert_eq!((args.command, args.optimized), (Command::NewDay(17), true));
+        assert!(!args.first);
+        assert!(parse(&["new-day", "17", "--first"]).unwrap().first);
+        assert!(parse(&["new-day"]).is_err());
+        assert!(parse(&["new-day", "26"]).is_err());
+
+        assert_eq!(parse(&["generate"]).unwrap().command, Command::Generate(None));
+        let args = parse(&["generate", "42", "--day", "16"]).unwrap();
+        assert_eq!(args.command, Command::Generate(Some(42)));
+        assert!(args.day(16) && !args.day(15));
+        assert!(parse(&["generate", "-1"]).is_err());
+        assert!(parse(&["generate", "x"]).is_err());
+        assert_eq!(parse(&["fuzz", "50"]).unwrap().command, Command::Fuzz(Some(50)));
+        assert!(parse(&["fuzz", "50", "60"]).is_err());
+    }
+
+    #[test]
+    fn snapshots() {
+        let args = parse(&["--save", "new.tsv", "--compare=old.tsv", "--threshold", "5%"]).unwrap();
+        assert_eq!(args.save.as_deref(), Some("new.tsv"));
+        assert_eq!(args.compare.as_deref(), Some("old.tsv"));
+        assert_eq!(args.threshold, 5.0);
+        assert_eq!(parse(&[]).unwrap().threshold, 10.0);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse(&["--day", "26"]).is_err());
+        assert!(parse(&["--days", "15-10"]).is_err());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--profile"]).is_err());
+        assert!(parse(&["--frobnicate"]).is_err());
+        assert!(parse(&["--threshold", "fast"]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Checks of the assumptions the implementations make about their input.
+//!
+//! Some implementations rely on properties that all real inputs have,
+//! but that the puzzle text doesn't promise, like small numbers or a small grid.
+//! Unlike a malformed input, an input that breaks such an assumption
+//! usually leads to a wrong answer instead of a panic.
+//! So the runner checks the assumptions of an implementation before running it,
+//! and falls back to the next implementation of the day if the input breaks any of them.
+//! The checks expect an input with the right format, see [`formats`](crate::formats).
+
+use std::fmt::Display;
+
+use crate::solutions::day17;
+
+/// An assumption of an implementation that an input breaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Broken {
+    /// Module path of the implementation, as in [`Solution::NAME`](crate::registry::Solution).
+    pub implementation: &'static str,
+    /// What the implementation assumes, and how the input breaks it.
+    pub assumption: String,
+}
+
+impl Display for Broken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} assumes {}", self.implementation, self.assumption)
+    }
+}
+
+/// Check the assumptions the implementations of a day make about an input,
+/// returning the ones it breaks.
+pub fn validate(day: usize, input: &str) -> Vec<Broken> {
+    let (implementation, broken) = match day {
+        1 => ("optimized::day01", day01(input)),
+        13 => ("optimized::day13", day13(input)),
+        14 => ("optimized::day14", day14(input)),
+        16 => ("optimized::day16", day16(input)),
+        17 => ("solutions::day17", day17(input)),
+        19 => ("optimized::day19", day19(input)),
+        24 => ("optimized::day24", day24(input)),
+        _ => return Vec::new(),
+    };
+    broken
+        .into_iter()
+        .map(|assumption| Broken { implementation, assumption })
+        .collect()
+}
+
+/// Numbers in the input with the line they are on, counting from 1.
+fn numbers(input: &str) -> impl Iterator<Item = (usize, u64)> + '_ {
+    input.lines().enumerate().flat_map(|(idx, line)| {
+        line.split(|c: char| !c.is_ascii_digit())
+            .filter_map(move |n| Some((idx + 1, n.parse().ok()?)))
+    })
+}
+
+fn day01(input: &str) -> Vec<String> {
+    let mut broken = Vec::new();
+    if let Some((line, _)) = numbers(input).find(|&(_, n)| n == 0) {
+        broken.push(format!(
+            "calories above 0, as it takes a 0 for an empty line, but line {line} is 0"
+        ));
+    }
+    // Each elf is only counted once the empty line after it is reached.
+    if !input.ends_with("\n\n") {
+        let mut totals: Vec<u64> = input
+            .split("\n\n")
+            .map(|elf| elf.lines().map(|n| n.parse::<u64>().unwrap()).sum())
+            .collect();
+        let last = totals.pop().unwrap();
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+        if last > totals.get(2).copied().unwrap_or(0) {
+            broken.push(format!(
+                "an empty line after the last elf if it is among the three carrying the most, \
+                 but it carries {last} and isn't followed by one"
+            ));
+        }
+    }
+    broken
+}
+
+fn day13(input: &str) -> Vec<String> {
+    let large = numbers(input).find(|&(_, n)| n > 10);
+    large
+        .map(|(line, n)| format!("values of at most 10, but line {line} has {n}"))
+        .into_iter()
+        .collect()
+}
+
+fn day14(input: &str) -> Vec<String> {
+    // The sand spreads from x=500 to both sides as far as the floor is deep,
+    // and its board only goes from x=0 to x=1000.
+    let points = input.lines().enumerate().flat_map(|(idx, line)| {
+        line.split(" -> ").map(move |point| {
+            let (x, y) = point.split_once(',').unwrap();
+            (idx + 1, x.parse::<u64>().unwrap(), y.parse::<u64>().unwrap())
+        })
+    });
+    let mut broken = Vec::new();
+    if let Some((line, _, y)) = points.clone().filter(|&(_, _, y)| y > 498).max_by_key(|p| p.2) {
+        broken.push(format!(
+            "rocks at most 498 deep to fit the sand on its board, but line {line} reaches {y}"
+        ));
+    }
+    if let Some((line, x, _)) = points.filter(|&(_, x, _)| x > 1000).max_by_key(|p| p.1) {
+        broken.push(format!(
+            "rocks at x from 0 to 1000 to fit them on its board, but line {line} reaches {x}"
+        ));
+    }
+    broken
+}
+
+fn day16(input: &str) -> Vec<String> {
+    let mut broken = Vec::new();
+    let valves: Vec<(&str, u64)> = input
+        .lines()
+        .map(|line| {
+            let name = &line["Valve ".len()..line.find(" has").unwrap()];
+            let flow = &line[line.find('=').unwrap() + 1..line.find(';').unwrap()];
+            (name, flow.parse().unwrap())
+        })
+        .collect();
+    let flowing = valves.iter().filter(|&&(_, flow)| flow > 0).count();
+    if flowing > 15 {
+        broken.push(format!(
+            "at most 15 valves with a flow rate to fit them and AA in a u16, \
+             but there are {flowing}"
+        ));
+    }
+    if let Some((_, flow)) = valves
+        .iter()
+        .find(|&&(name, flow)| name == "AA" && flow > 0)
+    {
+        broken.push(format!(
+            "valve AA has no flow rate, but it has a rate of {flow}"
+        ));
+    }
+    broken
+}
+
+fn day17(input: &str) -> Vec<String> {
+    // Enough rocks to see every combination of jet and rock shape.
+    let rocks = 5 * input.trim().len();
+    if day17::repeats_within(input, rocks) {
+        return Vec::new();
+    }
+    vec![format!(
+        "the tower repeats within {rocks} rocks to skip ahead in part 2, but it doesn't"
+    )]
+}
+
+fn day19(input: &str) -> Vec<String> {
+    let ids = input
+        .lines()
+        .map(|line| line.split([' ', ':']).nth(1).unwrap());
+    let mut ids = ids
+        .enumerate()
+        .map(|(idx, id)| (idx + 1, id.parse::<usize>().unwrap()));
+    let misnumbered = ids.find(|&(line, id)| line != id);
+    misnumbered
+        .map(|(line, id)| {
+            format!("blueprints numbered 1, 2, 3 and so on, but line {line} has blueprint {id}")
+        })
+        .into_iter()
+        .collect()
+}
+
+fn day24(input: &str) -> Vec<String> {
+    let mut broken = Vec::new();
+    let lines: Vec<&str> = input.lines().collect();
+    let width = lines[0].len() - 2;
+    if width > 127 {
+        broken.push(format!(
+            "a valley at most 127 wide to fit a row in a u128, but it's {width}"
+        ));
+    }
+    let walls = "#".repeat(width);
+    if lines[0] != format!("#.{walls}") || lines[lines.len() - 1] != format!("{walls}.#") {
+        broken.push(String::from(
+            "the entrance in the top left and the exit in the bottom right corner, \
+             but they are elsewhere",
+        ));
+    }
+    broken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken() {
+        let packets = "[1,[2]]\n[[10],3]\n\n[4]\n[11]\n";
+        let broken = validate(13, packets);
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].implementation, "optimized::day13");
+        assert_eq!(
+            broken[0].to_string(),
+            "optimized::day13 assumes values of at most 10, but line 5 has 11"
+        );
+        assert!(validate(13, &packets.replace("11", "10")).is_empty());
+
+        assert_eq!(validate(1, "1\n2\n\n3\n\n").len(), 0);
+        assert_eq!(validate(1, "1\n2\n\n0\n\n").len(), 1);
+        assert_eq!(validate(1, "1\n2\n\n4\n").len(), 1);
+
+        let jets = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+        assert!(validate(17, jets).is_empty());
+        assert_eq!(validate(17, "<\n").len(), 1);
+
+        let rocks = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        assert!(validate(14, rocks).is_empty());
+        let broken = validate(14, "1005,4 -> 1005,9 -> 1003,9\n");
+        assert_eq!(broken.len(), 1);
+        assert_eq!(
+            broken[0].assumption,
+            "rocks at x from 0 to 1000 to fit them on its board, but line 1 reaches 1005"
+        );
+        assert_eq!(validate(14, "500,499 -> 501,499\n").len(), 1);
+
+        let valley = "#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#\n";
+        assert!(validate(24, valley).is_empty());
+        assert_eq!(validate(24, &valley.replace("#.#", "##.")).len(), 1);
+        assert!(validate(2, "A Y\n").is_empty());
+    }
+}
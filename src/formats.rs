@@ -0,0 +1,402 @@
+//! Checks of the input format of each day.
+//!
+//! The solutions assume well-formed inputs and panic on anything else,
+//! usually from an `unwrap()` somewhere deep inside parsing.
+//! Checking the format up front lets us point out where an input is malformed instead.
+//! The checks only cover the syntax of an input, not the assumptions a solution makes about it.
+
+use crate::util::parse::{ParseError, Scanner};
+
+type Check = fn(&mut Scanner) -> Result<(), ParseError>;
+
+const CHECKS: [Check; 25] = [
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+];
+
+/// Check that an input has the format of the given day.
+pub fn check(day: usize, input: &str) -> Result<(), ParseError> {
+    let mut s = Scanner::new(input);
+    CHECKS[day - 1](&mut s)?;
+    s.end()
+}
+
+/// Lines of characters from `chars`, all of the same width.
+fn grid(s: &mut Scanner, chars: &str) -> Result<(), ParseError> {
+    let mut width = None;
+    while !s.is_eof() && s.peek() != Some('\n') {
+        let start = s.clone();
+        let row = s.many1("grid cell", |c| chars.contains(c))?;
+        if s.peek().is_some_and(|c| c != '\n') {
+            return s.error(format!("expected one of `{chars}`"));
+        }
+        let len = row.chars().count();
+        if *width.get_or_insert(len) != len {
+            return start.error(format!("row has width {len}, expected {}", width.unwrap()));
+        }
+        s.eol()?;
+    }
+    match width {
+        Some(_) => Ok(()),
+        None => s.error("expected grid"),
+    }
+}
+
+/// Groups of lines separated by empty lines.
+fn groups(
+    s: &mut Scanner,
+    mut line: impl FnMut(&mut Scanner) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    while !s.is_eof() {
+        if s.peek() != Some('\n') {
+            line(s)?;
+        }
+        s.eol()?;
+    }
+    Ok(())
+}
+
+fn day01(s: &mut Scanner) -> Result<(), ParseError> {
+    groups(s, |s| s.unsigned().map(drop))
+}
+
+fn day02(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        s.one_of("ABC")?;
+        s.literal(" ")?;
+        s.one_of("XYZ")?;
+        Ok(())
+    })
+}
+
+fn day03(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| s.word().map(drop))
+}
+
+fn day04(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        for sep in ["-", ",", "-", ""] {
+            s.unsigned()?;
+            s.literal(sep)?;
+        }
+        Ok(())
+    })
+}
+
+fn day05(s: &mut Scanner) -> Result<(), ParseError> {
+    // Rows of crates like `[A]`, with empty slots as three spaces.
+    while !s
+        .rest_of_line()
+        .trim_start()
+        .starts_with(|c: char| c.is_ascii_digit())
+    {
+        loop {
+            if s.try_literal("[") {
+                s.one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ")?;
+                s.literal("]")?;
+            } else {
+                s.literal("   ")?;
+            }
+            if !s.try_literal(" ") || s.peek() == Some('\n') {
+                break;
+            }
+        }
+        s.literal("\n")?;
+    }
+    // Stack numbers.
+    loop {
+        s.literal(" ")?;
+        s.unsigned()?;
+        s.literal(" ")?;
+        if !s.try_literal(" ") || s.peek() == Some('\n') {
+            break;
+        }
+    }
+    s.literal("\n\n")?;
+    s.lines(|s| {
+        for word in ["move ", " from ", " to "] {
+            s.literal(word)?;
+            s.unsigned()?;
+        }
+        Ok(())
+    })
+}
+
+fn day06(s: &mut Scanner) -> Result<(), ParseError> {
+    s.many1("lowercase letters", |c| c.is_ascii_lowercase())?;
+    s.eol()
+}
+
+fn day07(s: &mut Scanner) -> Result<(), ParseError> {
+    let name = |s: &mut Scanner| s.many1("file name", |c| c.is_ascii_graphic()).map(drop);
+    s.lines(|s| {
+        if s.try_literal("$ cd ") {
+            name(s)
+        } else if s.try_literal("$ ls") {
+            Ok(())
+        } else if s.try_literal("dir ") {
+            name(s)
+        } else {
+            s.unsigned()?;
+            s.literal(" ")?;
+            name(s)
+        }
+    })
+}
+
+fn day08(s: &mut Scanner) -> Result<(), ParseError> {
+    grid(s, "0123456789")
+}
+
+fn day09(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        s.one_of("UDLR")?;
+        s.literal(" ")?;
+        s.unsigned().map(drop)
+    })
+}
+
+fn day10(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(
+        |s| {
+            if s.try_literal("addx ") {
+                s.signed().map(drop)
+            } else {
+                s.literal("noop")
+            }
+        },
+    )
+}
+
+fn day11(s: &mut Scanner) -> Result<(), ParseError> {
+    while !s.is_eof() {
+        s.literal("Monkey ")?;
+        s.unsigned()?;
+        s.literal(":\n  Starting items: ")?;
+        if s.peek() != Some('\n') {
+            s.unsigned()?;
+            while s.try_literal(", ") {
+                s.unsigned()?;
+            }
+        }
+        s.literal("\n  Operation: new = old ")?;
+        s.one_of("+*")?;
+        s.literal(" ")?;
+        if !s.try_literal("old") {
+            s.unsigned()?;
+        }
+        s.literal("\n  Test: divisible by ")?;
+        s.unsigned()?;
+        s.literal("\n    If true: throw to monkey ")?;
+        s.unsigned()?;
+        s.literal("\n    If false: throw to monkey ")?;
+        s.unsigned()?;
+        s.eol()?;
+        if !s.is_eof() {
+            s.literal("\n")?;
+        }
+    }
+    Ok(())
+}
+
+fn day12(s: &mut Scanner) -> Result<(), ParseError> {
+    grid(s, "abcdefghijklmnopqrstuvwxyzSE")
+}
+
+fn day13(s: &mut Scanner) -> Result<(), ParseError> {
+    fn packet(s: &mut Scanner) -> Result<(), ParseError> {
+        if s.try_literal("[") {
+            if !s.try_literal("]") {
+                loop {
+                    packet(s)?;
+                    if !s.try_literal(",") {
+                        break;
+                    }
+                }
+                s.literal("]")?;
+            }
+            Ok(())
+        } else {
+            s.unsigned().map(drop)
+        }
+    }
+    while !s.is_eof() {
+        packet(s)?;
+        s.literal("\n")?;
+        packet(s)?;
+        s.eol()?;
+        if !s.is_eof() {
+            s.literal("\n")?;
+        }
+    }
+    Ok(())
+}
+
+fn day14(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| loop {
+        s.unsigned()?;
+        s.literal(",")?;
+        s.unsigned()?;
+        if !s.try_literal(" -> ") {
+            return Ok(());
+        }
+    })
+}
+
+fn day15(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        for word in ["Sensor at x=", ", y=", ": closest beacon is at x=", ", y="] {
+            s.literal(word)?;
+            s.signed()?;
+        }
+        Ok(())
+    })
+}
+
+fn day16(s: &mut Scanner) -> Result<(), ParseError> {
+    let valve = |s: &mut Scanner| s.many1("valve name", |c| c.is_ascii_uppercase()).map(drop);
+    s.lines(|s| {
+        s.literal("Valve ")?;
+        valve(s)?;
+        s.literal(" has flow rate=")?;
+        s.unsigned()?;
+        if !s.try_literal("; tunnel leads to valve ") {
+            s.literal("; tunnels lead to valves ")?;
+        }
+        valve(s)?;
+        while s.try_literal(", ") {
+            valve(s)?;
+        }
+        Ok(())
+    })
+}
+
+fn day17(s: &mut Scanner) -> Result<(), ParseError> {
+    s.many1("`<` or `>`", |c| c == '<' || c == '>')?;
+    s.eol()
+}
+
+fn day18(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        s.unsigned()?;
+        s.literal(",")?;
+        s.unsigned()?;
+        s.literal(",")?;
+        s.unsigned().map(drop)
+    })
+}
+
+fn day19(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| {
+        for word in [
+            "Blueprint ",
+            ": Each ore robot costs ",
+            " ore. Each clay robot costs ",
+            " ore. Each obsidian robot costs ",
+            " ore and ",
+            " clay. Each geode robot costs ",
+            " ore and ",
+        ] {
+            s.literal(word)?;
+            s.unsigned()?;
+        }
+        s.literal(" obsidian.")
+    })
+}
+
+fn day20(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| s.signed().map(drop))
+}
+
+fn day21(s: &mut Scanner) -> Result<(), ParseError> {
+    let name = |s: &mut Scanner| s.many1("monkey name", |c| c.is_ascii_lowercase()).map(drop);
+    s.lines(|s| {
+        name(s)?;
+        s.literal(": ")?;
+        if s.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return s.unsigned().map(drop);
+        }
+        name(s)?;
+        s.literal(" ")?;
+        s.one_of("+-*/")?;
+        s.literal(" ")?;
+        name(s)
+    })
+}
+
+fn day22(s: &mut Scanner) -> Result<(), ParseError> {
+    // Rows of the map have differing widths.
+    while s.peek() != Some('\n') {
+        s.many1("map cell", |c| " .#".contains(c))?;
+        s.literal("\n")?;
+    }
+    s.literal("\n")?;
+    s.unsigned()?;
+    while s.peek().is_some_and(|c| c != '\n') {
+        s.one_of("LR")?;
+        s.unsigned()?;
+    }
+    s.eol()
+}
+
+fn day23(s: &mut Scanner) -> Result<(), ParseError> {
+    grid(s, ".#")
+}
+
+fn day24(s: &mut Scanner) -> Result<(), ParseError> {
+    grid(s, "#.<>^v")
+}
+
+fn day25(s: &mut Scanner) -> Result<(), ParseError> {
+    s.lines(|s| s.many1("SNAFU digit", |c| "=-012".contains(c)).map(drop))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        assert!(check(4, "2-4,6-8\n2-3,4-5\n").is_ok());
+        assert!(check(
+            5,
+            "    [D]    \n[N] [C]    \n 1   2   3 \n\nmove 1 from 2 to 1\n"
+        )
+        .is_ok());
+        assert!(check(13, "[1,[2,[]]]\n[[1],4]\n\n[]\n[3]\n").is_ok());
+        assert!(check(22, "  ..#\n  #..\n...\n\n10R5L5\n").is_ok());
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let inputs = [
+            (4, "2-4,6-8\n2-3,4-5\n"),
+            (5, "    [D]    \n[N] [C]    \n 1   2   3 \n\nmove 1 from 2 to 1\n"),
+            (8, "123\n456\n"),
+            (13, "[1,[2,[]]]\n[[1],4]\n\n[]\n[3]\n"),
+            (22, "  ..#\n  #..\n...\n\n10R5L5\n"),
+        ];
+        for (day, input) in inputs {
+            let input = input.replace('\n', "\r\n");
+            assert_eq!(check(day, &input), Ok(()), "day {day}");
+        }
+        let err = check(4, "2-4,6-8\r\n2-3;4-5\r\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+    }
+
+    #[test]
+    fn invalid() {
+        let err = check(4, "2-4,6-8\n2-3;4-5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        let err = check(8, "123\n45\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "row has width 2, expected 3");
+        let err = check(10, "noop\naddx\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = check(13, "[1,[2]\n[3]\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (1, 7, "expected `]`")
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::{Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use aoc2022::formats;
+use aoc2022::generator;
+use aoc2022::profile::Profile;
+use aoc2022::registry;
+
+use crate::args::{Args, DAYS};
+use crate::{panic_message, Job, Jobs};
+
+/// Time to spend on shrinking each input, as some implementations take a while.
+const SHRINK_TIME: Duration = Duration::from_secs(10);
+
+/// Time after which an implementation is considered stuck on an input, and stopped.
+const RUN_TIME: Duration = Duration::from_secs(10);
+
+/// Environment variable naming the implementation to run in a child process of [`run`].
+pub const JOB: &str = "AOC_FUZZ_JOB";
+
+/// Answers of an implementation as JSON, which keeps their type so that they are equal
+/// exactly when the answers are, or why it failed.
+type Outcome = Result<[String; 2], String>;
+
+/// How the implementations of a day disagree,
+/// relative to the first one that supports the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Agrees,
+    /// The parts with a different answer.
+    Differs([bool; 2]),
+    /// Panicked or got stuck, even if the first one did too.
+    Panicked,
+}
+
+/// Run every implementation of the selected days on generated inputs from seeds `0..runs`.
+/// Each input they disagree on, or that one of them panics or gets stuck on,
+/// is shrunk while they keep failing the same way,
+/// and saved to a profile `fuzz-<DAY>-<SEED>` in `data/` to reproduce with `--cross-check`.
+/// These profiles are only used when selected with `--profile`, see [`Profile::FUZZ_PREFIX`].
+pub fn fuzz(args: &Args, runs: u64) {
+    let mut jobs = Jobs { overrides: &args.params, jobs: Vec::new() };
+    registry::visit_all(&mut jobs);
+    let mut disagreements = 0;
+    for day in DAYS.filter(|&day| args.day(day)) {
+        let jobs: Vec<&Job> = jobs.jobs.iter().filter(|job| job.day == day).collect();
+        let mut found = 0;
+        for seed in 0..runs {
+            let Some((input, statuses)) = disagreement(&jobs, day, seed) else {
+                continue;
+            };
+            found += 1;
+            let deadline = Instant::now() + SHRINK_TIME;
+            let keep = |input: &str| {
+                Instant::now() < deadline
+                    && formats::check(day, input).is_ok()
+                    && compare(&run_all(&jobs, input)) == statuses
+            };
+            let shrunk = shrink(&input, keep);
+            let outcomes = run_all(&jobs, &shrunk);
+            println!("Day {day}, seed {seed}:");
+            for (job, outcome) in jobs.iter().zip(&outcomes) {
+                match outcome {
+                    Some(Ok([res1, res2])) => {
+                        println!("    {} answered {res1} and {res2}", job.name)
+                    }
+                    Some(Err(message)) => println!("    {} failed: {message}", job.name),
+                    None => println!("    {} doesn't support the input", job.name),
+                }
+            }
+            let name = format!("{}{day}-{seed}", Profile::FUZZ_PREFIX);
+            let profile = Profile { path: Path::new("data").join(&name), name };
+            let path = profile.input_path(day);
+            let written = std::fs::create_dir_all(path.parent().unwrap())
+                .and_then(|()| std::fs::write(&path, &shrunk));
+            match written {
+                Ok(()) => println!(
+                    "    shrunk from {} to {} lines in {}",
+                    input.lines().count(),
+                    shrunk.lines().count(),
+                    path.display()
+                ),
+                Err(err) => println!("    couldn't write `{}`: {err}", path.display()),
+            }
+        }
+        println!("Day {day}: {found} disagreements in {runs} inputs\n");
+        disagreements += found;
+    }
+    if disagreements > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Run the implementations of a day on the input generated from a seed,
+/// returning the input and how they compare if any of them disagrees, panics or gets stuck.
+fn disagreement(jobs: &[&Job], day: usize, seed: u64) -> Option<(String, Vec<Status>)> {
+    let input = generator::generate(day, seed);
+    let statuses = compare(&run_all(jobs, &input));
+    let agree = statuses.iter().all(|status| *status == Status::Agrees);
+    (!agree).then_some((input, statuses))
+}
+
+/// Run each implementation that supports the input, like the runner does.
+fn run_all(jobs: &[&Job], input: &str) -> Vec<Option<Outcome>> {
+    jobs.iter()
+        .map(|job| (job.validate)(input).is_empty().then(|| run(job, input)))
+        .collect()
+}
+
+/// Run an implementation in a child process, so that it can be stopped
+/// if it takes longer than [`RUN_TIME`], and so that crashes are caught like panics.
+fn run(job: &Job, input: &str) -> Outcome {
+    let exe = std::env::current_exe().map_err(|err| format!("couldn't find the runner: {err}"))?;
+    let mut child = Command::new(exe)
+        .args(std::env::args_os().skip(1))
+        .env(JOB, job.name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("couldn't start: {err}"))?;
+    // The child reads all of its input before it writes anything, so this can't block.
+    // Writing fails if the child is gone already, which the missing output reports.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    let mut stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        let _ = sender.send(output);
+    });
+    let output = receiver.recv_timeout(RUN_TIME);
+    if output.is_err() {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+    let output = output.map_err(|_| format!("didn't finish within {RUN_TIME:?}"))?;
+    match output.split_once('\n') {
+        Some(("answered", answers)) => match answers.lines().collect::<Vec<_>>()[..] {
+            [res1, res2] => Ok([res1.to_owned(), res2.to_owned()]),
+            _ => Err(format!("answered `{answers}`")),
+        },
+        Some(("panicked", message)) => Err(message.trim_end().to_owned()),
+        _ => Err(String::from("crashed")),
+    }
+}
+
+/// Run the implementation named in [`JOB`] on the input from stdin for [`run`],
+/// writing its answers or the message it panicked with to stdout.
+pub fn child(args: &Args, name: &str) {
+    let mut jobs = Jobs { overrides: &args.params, jobs: Vec::new() };
+    registry::visit_all(&mut jobs);
+    let job = jobs.jobs.iter().find(|job| job.name == name).expect("unknown implementation");
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).expect("couldn't read the input");
+    panic::set_hook(Box::new(|_| {}));
+    match panic::catch_unwind(AssertUnwindSafe(|| (job.run)(&input))) {
+        Ok([res1, res2]) => println!("answered\n{}\n{}", res1.to_json(), res2.to_json()),
+        Err(payload) => println!("panicked\n{}", panic_message(payload.as_ref())),
+    }
+}
+
+/// Compare each outcome to the first one,
+/// leaving out the implementations that don't support the input.
+fn compare(outcomes: &[Option<Outcome>]) -> Vec<Status> {
+    let reference = outcomes.iter().flatten().next();
+    outcomes
+        .iter()
+        .map(|outcome| match (reference, outcome) {
+            (_, None) | (None, _) => Status::Agrees,
+            (_, Some(Err(_))) | (Some(Err(_)), _) => Status::Panicked,
+            (Some(Ok(reference)), Some(Ok(answers))) => {
+                // A part that is left out agrees with anything, like in `Answer::agrees`.
+                let agrees = |a: &str, b: &str| a == b || a == "null" || b == "null";
+                let differs = [0, 1].map(|part| !agrees(&answers[part], &reference[part]));
+                if differs.contains(&true) {
+                    Status::Differs(differs)
+                } else {
+                    Status::Agrees
+                }
+            }
+        })
+        .collect()
+}
+
+/// Shrink an input as long as `keep` holds for it: first by removing groups of lines
+/// separated by empty lines, then single lines, and finally by making numbers smaller.
+fn shrink(input: &str, keep: impl Fn(&str) -> bool) -> String {
+    let mut groups = Vec::new();
+    let mut rest = input;
+    while let Some(end) = rest.find("\n\n") {
+        groups.push(&rest[..end + 2]);
+        rest = &rest[end + 2..];
+    }
+    groups.push(rest);
+    let input = remove_chunks(groups, &keep);
+    let mut input = remove_chunks(input.split_inclusive('\n').collect(), &keep);
+    loop {
+        let mut progress = false;
+        let mut start = 0;
+        while let Some((from, to)) = number(&input, start) {
+            // Numbers too long to parse, like rows of digits, are left alone.
+            let n: i64 = input[from..to].parse().unwrap_or(0);
+            let replace = |m: i64| format!("{}{m}{}", &input[..from], &input[to..]);
+            let smaller = [0, n / 2, n - n.signum()]
+                .into_iter()
+                .find(|&m| m.abs() < n.abs() && keep(&replace(m)));
+            start = to;
+            if let Some(m) = smaller {
+                input = replace(m);
+                start = from + m.to_string().len();
+                progress = true;
+            }
+        }
+        if !progress {
+            return input;
+        }
+    }
+}
+
+/// Remove as many parts of the input as `keep` allows,
+/// starting with large chunks of consecutive parts and ending with single parts.
+fn remove_chunks(mut parts: Vec<&str>, keep: impl Fn(&str) -> bool) -> String {
+    let mut chunk = parts.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < parts.len() {
+            let end = (start + chunk).min(parts.len());
+            let candidate = [&parts[..start], &parts[end..]].concat();
+            if keep(&candidate.concat()) {
+                parts = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    parts.concat()
+}
+
+/// Byte range of the next number from `start` on, including its sign.
+/// A `-` between digits, like in `2-4`, is a separator rather than a sign.
+fn number(input: &str, start: usize) -> Option<(usize, usize)> {
+    let bytes = input.as_bytes();
+    let digit = start + bytes[start..].iter().position(u8::is_ascii_digit)?;
+    let signed = digit > start
+        && bytes[digit - 1] == b'-'
+        && (digit < 2 || !bytes[digit - 2].is_ascii_alphanumeric());
+    let from = if signed { digit - 1 } else { digit };
+    let len = bytes[digit..].iter().position(|b| !b.is_ascii_digit());
+    Some((from, digit + len.unwrap_or(bytes.len() - digit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinking() {
+        // Only inputs with a line containing a number of at least 7 are interesting.
+        let keep = |input: &str| {
+            input.lines().any(|line| {
+                line.split(' ')
+                    .any(|w| w.parse().is_ok_and(|n: i32| n >= 7))
+            })
+        };
+        let input = "a 1\nb 20 3\nc 4\nd 9 x\n";
+        assert_eq!(shrink(input, keep), "d 7 x\n");
+        assert_eq!(shrink("-12\n", |input| input.starts_with('-')), "-1\n");
+        assert_eq!(shrink("2-4\n", |input| input.contains('-')), "0-0\n");
+
+        let input = "a\nb\n\nc\nd\n\ne\nf\n";
+        let keep = |input: &str| input.contains("c\nd\n") && input.contains("\n\n");
+        assert_eq!(shrink(input, keep), "c\nd\n\n");
+    }
+}
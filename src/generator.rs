@@ -0,0 +1,118 @@
+//! Random inputs for each day, generated from a seed.
+//!
+//! The real inputs are personal and shouldn't be shared, and there are only a handful of them.
+//! The generated inputs follow the rules of each puzzle, both the stated ones and the ones
+//! that all real inputs happen to follow, so any solution should work on them.
+//! They have about the size of the real inputs, but nothing is known about their answers.
+
+use crate::util::rng::Rng;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+type Generator = fn(&mut Rng) -> String;
+
+const GENERATORS: [Generator; 25] = [
+    day01::generate,
+    day02::generate,
+    day03::generate,
+    day04::generate,
+    day05::generate,
+    day06::generate,
+    day07::generate,
+    day08::generate,
+    day09::generate,
+    day10::generate,
+    day11::generate,
+    day12::generate,
+    day13::generate,
+    day14::generate,
+    day15::generate,
+    day16::generate,
+    day17::generate,
+    day18::generate,
+    day19::generate,
+    day20::generate,
+    day21::generate,
+    day22::generate,
+    day23::generate,
+    day24::generate,
+    day25::generate,
+];
+
+/// Generate an input for the given day. The same seed always gives the same input.
+pub fn generate(day: usize, seed: u64) -> String {
+    GENERATORS[day - 1](&mut Rng::new(seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats;
+    use crate::registry::{self, Solution, Visitor};
+
+    #[test]
+    fn well_formed() {
+        for day in 1..=25 {
+            for seed in 0..3 {
+                let input = generate(day, seed);
+                if let Err(err) = formats::check(day, &input) {
+                    panic!("day {day} with seed {seed}: {err}");
+                }
+                assert_eq!(input, generate(day, seed), "day {day} with seed {seed}");
+            }
+            assert_ne!(generate(day, 0), generate(day, 1), "day {day}");
+        }
+    }
+
+    /// Days whose reference implementation searches exhaustively and takes seconds per input,
+    /// so the optimized one is run instead.
+    const SLOW: [usize; 2] = [16, 19];
+
+    /// Runs the reference implementation of each day on a few inputs,
+    /// which panics on inputs that break the rules of the puzzle, like an unsolvable valley.
+    struct Solve;
+
+    impl Visitor for Solve {
+        fn visit<S: Solution>(&mut self) {
+            let module = if SLOW.contains(&S::DAY) { "optimized::" } else { "solutions::" };
+            if !S::NAME.starts_with(module) {
+                return;
+            }
+            for seed in 0..3 {
+                let input = generate(S::DAY, seed);
+                assert!(S::validate(&input).is_empty(), "{} with seed {seed}", S::NAME);
+                let solved = std::panic::catch_unwind(|| drop(S::run(&input)));
+                assert!(solved.is_ok(), "{} panicked with seed {seed}", S::NAME);
+            }
+        }
+    }
+
+    #[test]
+    fn solvable() {
+        registry::visit_all(&mut Solve);
+    }
+}
@@ -0,0 +1,13 @@
+use crate::util::rng::Rng;
+
+/// About 250 elves carrying 1 to 15 snacks each, none of which is worth 0 calories.
+pub fn generate(rng: &mut Rng) -> String {
+    let elves: Vec<String> = (0..rng.range(200..=300))
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| format!("{}\n", rng.range(1000u32..=69999)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
@@ -0,0 +1,13 @@
+use crate::util::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> String {
+    (0..2500)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+use crate::util::rng::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Groups of three rucksacks. The two compartments of a rucksack share exactly one item type,
+/// and the three rucksacks of a group share exactly one item type, their badge.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for _ in 0..100 {
+        let mut items = ITEMS.to_vec();
+        rng.shuffle(&mut items);
+        // The other items of each compartment come from its own pool,
+        // so they can't end up being shared by accident.
+        let (badge, shared, pools) = (items[0], &items[1..4], &items[4..]);
+        let mut pools = pools.chunks(8);
+        for &common in shared {
+            let len = rng.range(8..=24);
+            let badge_side = rng.index(2);
+            for side in 0..2 {
+                let pool = pools.next().unwrap();
+                let mut compartment = vec![common];
+                if side == badge_side {
+                    compartment.push(badge);
+                }
+                while compartment.len() < len {
+                    compartment.push(*rng.choose(pool));
+                }
+                rng.shuffle(&mut compartment);
+                out.extend(compartment.into_iter().map(char::from));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
@@ -0,0 +1,14 @@
+use crate::util::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> String {
+    let mut section = || {
+        let start = rng.range(1..=99);
+        (start, rng.range(start..=99))
+    };
+    (0..1000)
+        .map(|_| {
+            let ((a, b), (c, d)) = (section(), section());
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
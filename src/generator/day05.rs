@@ -0,0 +1,47 @@
+use crate::util::rng::Rng;
+
+const STACKS: usize = 9;
+
+/// Nine stacks of at most eight crates, and moves that never take more crates than a stack has.
+/// No move empties a stack, so that each stack has a crate on top at the end.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut stacks: Vec<Vec<u8>> = (0..STACKS)
+        .map(|_| {
+            (0..rng.range(1..=8))
+                .map(|_| rng.range(b'A'..=b'Z'))
+                .collect()
+        })
+        .collect();
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    let mut out = String::new();
+    for row in (0..height).rev() {
+        let crates: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(row) {
+                Some(&c) => format!("[{}]", char::from(c)),
+                None => String::from("   "),
+            })
+            .collect();
+        out += &crates.join(" ");
+        out.push('\n');
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|n| format!(" {n} ")).collect();
+    out += &numbers.join(" ");
+    out += "\n\n";
+
+    for _ in 0..500 {
+        let from = loop {
+            let from = rng.index(STACKS);
+            if stacks[from].len() > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.range(1..=STACKS - 1)) % STACKS;
+        let count = rng.range(1..=stacks[from].len() - 1);
+        let split = stacks[from].len() - count;
+        let moved = stacks[from].split_off(split);
+        stacks[to].extend(moved.into_iter().rev());
+        out += &format!("move {count} from {} to {}\n", from + 1, to + 1);
+    }
+    out
+}
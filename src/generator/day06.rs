@@ -0,0 +1,42 @@
+use crate::util::rng::Rng;
+
+const LEN: usize = 4096;
+
+/// A datastream whose first start-of-packet marker comes well before
+/// its first start-of-message marker.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut stream = vec![rng.range(b'a'..=b'z')];
+    let packet = rng.range(500..=1500);
+    let message = rng.range(2000..=3500);
+    without_marker(rng, &mut stream, 4, packet);
+    marker(rng, &mut stream, 4);
+    without_marker(rng, &mut stream, 14, message);
+    marker(rng, &mut stream, 14);
+    while stream.len() < LEN {
+        stream.push(rng.range(b'a'..=b'z'));
+    }
+    String::from_utf8(stream).unwrap() + "\n"
+}
+
+/// Extend the stream to `len` letters without any `n` consecutive letters being different.
+fn without_marker(rng: &mut Rng, stream: &mut Vec<u8>, n: usize, len: usize) {
+    while stream.len() < len {
+        let window = &stream[stream.len().saturating_sub(n - 1)..];
+        let repeats = window
+            .iter()
+            .enumerate()
+            .any(|(i, c)| window[..i].contains(c));
+        let c = if repeats { rng.range(b'a'..=b'z') } else { *rng.choose(window) };
+        stream.push(c);
+    }
+}
+
+/// Append a marker of `n` different letters, which doesn't start any earlier.
+fn marker(rng: &mut Rng, stream: &mut Vec<u8>, n: usize) {
+    // Repeating the last letter rules out the windows that end before the marker does.
+    let last = *stream.last().unwrap();
+    stream.push(last);
+    let mut letters: Vec<u8> = (b'a'..=b'z').filter(|&c| c != last).collect();
+    rng.shuffle(&mut letters);
+    stream.extend(&letters[..n - 1]);
+}
@@ -0,0 +1,95 @@
+use std::ops::RangeInclusive;
+
+use crate::util::rng::Rng;
+
+struct Dir {
+    name: String,
+    depth: usize,
+    files: Vec<(u32, String)>,
+    dirs: Vec<usize>,
+}
+
+/// A shell session that lists every directory once, walking the tree depth-first from `/`.
+/// The total size stays between 40 and 70 million, so that there is something to delete
+/// for the update but not more than the disk holds.
+pub fn generate(rng: &mut Rng) -> String {
+    let root = Dir { name: String::from("/"), depth: 0, files: vec![], dirs: vec![] };
+    let mut dirs = vec![root];
+    for _ in 0..rng.range(150..=200) {
+        let parent = loop {
+            let parent = rng.index(dirs.len());
+            if dirs[parent].depth < 10 {
+                break parent;
+            }
+        };
+        let name = unique_name(rng, &dirs, parent, false);
+        let child = dirs.len();
+        dirs[parent].dirs.push(child);
+        let depth = dirs[parent].depth + 1;
+        dirs.push(Dir { name, depth, files: vec![], dirs: vec![] });
+    }
+    let target = rng.range(41_000_000..=50_000_000);
+    let mut total = 0;
+    while total < target {
+        let dir = rng.index(dirs.len());
+        let size = rng.range(1000..=300_000);
+        let name = unique_name(rng, &dirs, dir, true);
+        dirs[dir].files.push((size, name));
+        total += size;
+    }
+
+    let mut out = String::from("$ cd /\n");
+    session(rng, &dirs, 0, &mut out);
+    // Nobody bothers to walk back up at the end.
+    while let Some(rest) = out.strip_suffix("$ cd ..\n") {
+        out.truncate(rest.len());
+    }
+    out
+}
+
+fn session(rng: &mut Rng, dirs: &[Dir], dir: usize, out: &mut String) {
+    let files = dirs[dir]
+        .files
+        .iter()
+        .map(|(size, name)| format!("{size} {name}"));
+    let mut entries: Vec<String> = files.collect();
+    entries.extend(
+        dirs[dir]
+            .dirs
+            .iter()
+            .map(|&d| format!("dir {}", dirs[d].name)),
+    );
+    rng.shuffle(&mut entries);
+    out.push_str("$ ls\n");
+    for entry in entries {
+        out.push_str(&entry);
+        out.push('\n');
+    }
+    for &child in &dirs[dir].dirs {
+        out.push_str(&format!("$ cd {}\n", dirs[child].name));
+        session(rng, dirs, child, out);
+        out.push_str("$ cd ..\n");
+    }
+}
+
+/// A name that isn't used yet in the directory, where `dirs` holds all directories.
+/// File names may have an extension.
+fn unique_name(rng: &mut Rng, dirs: &[Dir], dir: usize, file: bool) -> String {
+    loop {
+        let mut name = word(rng, 1..=8);
+        if file && rng.chance(0.5) {
+            name = format!("{name}.{}", word(rng, 3..=3));
+        }
+        let file_taken = dirs[dir].files.iter().any(|(_, n)| *n == name);
+        let dir_taken = dirs[dir].dirs.iter().any(|&d| dirs[d].name == name);
+        if !file_taken && !dir_taken {
+            return name;
+        }
+    }
+}
+
+fn word(rng: &mut Rng, len: RangeInclusive<usize>) -> String {
+    (0..rng.range(len))
+        .map(|_| char::from(rng.range(b'a'..=b'z')))
+        .collect()
+}
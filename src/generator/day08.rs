@@ -0,0 +1,10 @@
+use crate::util::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for _ in 0..99 {
+        out.extend((0..99).map(|_| char::from(rng.range(b'0'..=b'9'))));
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,13 @@
+use crate::util::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> String {
+    (0..2000)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['U', 'D', 'L', 'R']),
+                rng.range(1..=19)
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+use crate::ocr;
+use crate::util::rng::Rng;
+
+const WIDTH: usize = 40;
+const CYCLES: usize = 240;
+/// Sprite positions that make a difference: the sprite is off the screen at either end.
+const XS: std::ops::RangeInclusive<i32> = -2..=41;
+
+/// A program that draws eight letters of the font on the CRT, like the real ones do.
+pub fn generate(rng: &mut Rng) -> String {
+    let letters: Vec<char> = ocr::letters().collect();
+    loop {
+        let text: String = (0..WIDTH / 5).map(|_| *rng.choose(&letters)).collect();
+        let screen = ocr::draw(&text).unwrap();
+        let pixel = |cycle: usize| screen.get(cycle % WIDTH, cycle / WIDTH);
+        if let Some(program) = program(rng, pixel) {
+            return program;
+        }
+    }
+}
+
+/// A random program that lights up exactly the given pixels, if there is one.
+fn program(rng: &mut Rng, pixel: impl Fn(usize) -> bool) -> Option<String> {
+    let lit = |cycle: usize, x: i32| (x - (cycle % WIDTH) as i32).abs() <= 1;
+    let draws = |cycle: usize, x: i32| cycle >= CYCLES || lit(cycle, x) == pixel(cycle);
+    let index = |x: i32| (x - XS.start()) as usize;
+    // Whether the program can still draw the rest of the screen,
+    // starting at each cycle with each position of the sprite.
+    // An `addx 0` isn't any use, so `addx` always moves the sprite.
+    let mut possible = vec![vec![true; XS.count()]; CYCLES + 2];
+    for cycle in (0..CYCLES).rev() {
+        let after_addx = XS.filter(|&x| possible[cycle + 2][index(x)]).count();
+        for x in XS {
+            let noop = possible[cycle + 1][index(x)];
+            let moves = after_addx - usize::from(possible[cycle + 2][index(x)]) > 0;
+            let addx = draws(cycle + 1, x) && moves;
+            possible[cycle][index(x)] = draws(cycle, x) && (noop || addx);
+        }
+    }
+    let targets = |possible: &[bool], x: i32| {
+        XS.filter(|&to| to != x && possible[index(to)])
+            .collect::<Vec<_>>()
+    };
+    if !possible[0][index(1)] {
+        return None;
+    }
+    let (mut cycle, mut x) = (0, 1);
+    let mut out = String::new();
+    while cycle < CYCLES {
+        let addx = if draws(cycle + 1, x) { targets(&possible[cycle + 2], x) } else { vec![] };
+        if addx.is_empty() || possible[cycle + 1][index(x)] && rng.chance(0.3) {
+            out.push_str("noop\n");
+            cycle += 1;
+        } else {
+            let to = *rng.choose(&addx);
+            out.push_str(&format!("addx {}\n", to - x));
+            cycle += 2;
+            x = to;
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,106 @@
+use crate::util::rng::Rng;
+
+const PRIMES: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+#[derive(Clone)]
+enum Op {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+#[derive(Clone)]
+struct Monkey {
+    items: Vec<u64>,
+    op: Op,
+    test: u64,
+    targets: [usize; 2],
+}
+
+/// Eight monkeys that test for different primes and throw to two other monkeys each.
+/// One monkey squares the worry level, so the levels of part 1 have to be checked for overflow.
+pub fn generate(rng: &mut Rng) -> String {
+    loop {
+        let monkeys = monkeys(rng);
+        if fits(monkeys.clone()) {
+            return render(&monkeys);
+        }
+    }
+}
+
+fn monkeys(rng: &mut Rng) -> Vec<Monkey> {
+    let count = PRIMES.len();
+    let mut tests = PRIMES;
+    rng.shuffle(&mut tests);
+    let mut ops = vec![
+        Op::Square,
+        Op::Mul(rng.range(2..=19)),
+        Op::Mul(rng.range(2..=19)),
+    ];
+    while ops.len() < count {
+        ops.push(Op::Add(rng.range(1..=8)));
+    }
+    rng.shuffle(&mut ops);
+    let others = |rng: &mut Rng, idx| (idx + rng.range(1..=count - 1)) % count;
+    ops.into_iter()
+        .zip(tests)
+        .enumerate()
+        .map(|(idx, (op, test))| {
+            let pos = others(rng, idx);
+            let neg = loop {
+                let neg = others(rng, idx);
+                if neg != pos {
+                    break neg;
+                }
+            };
+            let items = (0..rng.range(1..=8)).map(|_| rng.range(50..=99)).collect();
+            Monkey { items, op, test, targets: [pos, neg] }
+        })
+        .collect()
+}
+
+/// Whether the worry levels of part 1 stay within a `u64`.
+fn fits(mut monkeys: Vec<Monkey>) -> bool {
+    for _ in 0..20 {
+        for idx in 0..monkeys.len() {
+            for item in std::mem::take(&mut monkeys[idx].items) {
+                let new = match monkeys[idx].op {
+                    Op::Add(n) => item.checked_add(n),
+                    Op::Mul(n) => item.checked_mul(n),
+                    Op::Square => item.checked_mul(item),
+                };
+                let Some(new) = new.map(|new| new / 3) else {
+                    return false;
+                };
+                let target = monkeys[idx].targets[usize::from(new % monkeys[idx].test != 0)];
+                monkeys[target].items.push(new);
+            }
+        }
+    }
+    true
+}
+
+fn render(monkeys: &[Monkey]) -> String {
+    let blocks: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(idx, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(u64::to_string).collect();
+            let op = match monkey.op {
+                Op::Add(n) => format!("+ {n}"),
+                Op::Mul(n) => format!("* {n}"),
+                Op::Square => String::from("* old"),
+            };
+            format!(
+                "Monkey {idx}:\n  Starting items: {}\n  Operation: new = old {op}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n",
+                items.join(", "),
+                monkey.test,
+                monkey.targets[0],
+                monkey.targets[1],
+            )
+        })
+        .collect();
+    blocks.join("\n")
+}
@@ -0,0 +1,51 @@
+use crate::util::{
+    grid::{pos, Grid},
+    pathfinding::bfs,
+    rng::Rng,
+};
+
+/// A hill that rises towards the best signal, with some noise in its slopes.
+/// There is always a path from the start to the best signal.
+pub fn generate(rng: &mut Rng) -> String {
+    loop {
+        let (width, height) = (rng.range(60..=160), rng.range(20..=41));
+        let end = pos(
+            rng.range(width / 4..=width * 3 / 4),
+            rng.range(height / 4..=height * 3 / 4),
+        );
+        let slope = rng.range(2..=4);
+        let mut grid = Grid::new_filled(width, height, 0u8);
+        for y in 0..height {
+            for x in 0..width {
+                let dist = pos(x, y).dist(&end) + rng.range(0..=slope);
+                grid[pos(x, y)] = 25 - (dist / slope).min(25) as u8;
+            }
+        }
+        grid[end] = 25;
+        let lowest: Vec<_> = (0..width * height)
+            .map(|idx| pos(idx % width, idx / width))
+            .filter(|&p| grid[p] == 0)
+            .collect();
+        if lowest.is_empty() {
+            continue;
+        }
+        let start = *rng.choose(&lowest);
+        let climbable = |p, n| grid[n] as i16 - grid[p] as i16 <= 1;
+        if bfs(&grid, start, climbable, |_, p| p == end).is_none() {
+            continue;
+        }
+
+        let mut out = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                out.push(match pos(x, y) {
+                    p if p == start => 'S',
+                    p if p == end => 'E',
+                    p => char::from(b'a' + grid[p]),
+                });
+            }
+            out.push('\n');
+        }
+        return out;
+    }
+}
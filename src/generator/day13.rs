@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use crate::util::rng::Rng;
+
+#[derive(Clone)]
+enum Packet {
+    Int(u8),
+    List(Vec<Packet>),
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            (Packet::Int(a), b) => Packet::List(vec![Packet::Int(*a)]).cmp(b),
+            (a, Packet::Int(b)) => a.cmp(&Packet::List(vec![Packet::Int(*b)])),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Int(n) => write!(f, "{n}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Pairs of packets with integers up to 10, nested at most four lists deep.
+/// The packets of a pair never compare equal, and no packet compares equal to a divider packet.
+/// About half of the pairs only differ a little, so that comparing them takes a while.
+pub fn generate(rng: &mut Rng) -> String {
+    let dividers = [2, 6].map(|n| Packet::List(vec![Packet::List(vec![Packet::Int(n)])]));
+    let mut pairs = Vec::new();
+    while pairs.len() < 150 {
+        let left = list(rng, 1);
+        let mut right = left.clone();
+        if rng.chance(0.5) {
+            mutate(rng, &mut right, 1);
+        } else {
+            right = list(rng, 1);
+        }
+        // Packets that compare equal, like `[1]` and `[[1]]`, have no order.
+        if left != right && !dividers.contains(&left) && !dividers.contains(&right) {
+            pairs.push(format!("{left}\n{right}\n"));
+        }
+    }
+    pairs.join("\n")
+}
+
+fn list(rng: &mut Rng, depth: usize) -> Packet {
+    Packet::List((0..rng.range(0..=5)).map(|_| item(rng, depth)).collect())
+}
+
+fn item(rng: &mut Rng, depth: usize) -> Packet {
+    if depth < 4 && rng.chance(0.3) {
+        list(rng, depth + 1)
+    } else {
+        Packet::Int(rng.range(0..=10))
+    }
+}
+
+/// Make a small change somewhere in the packet.
+fn mutate(rng: &mut Rng, packet: &mut Packet, depth: usize) {
+    match packet {
+        Packet::List(items) if !items.is_empty() && rng.chance(0.6) => {
+            let idx = rng.index(items.len());
+            mutate(rng, &mut items[idx], depth + 1);
+        }
+        Packet::List(items) if !items.is_empty() && rng.chance(0.5) => {
+            items.pop();
+        }
+        Packet::List(items) => items.push(item(rng, depth)),
+        Packet::Int(n) if depth < 4 && rng.chance(0.3) => {
+            *packet = Packet::List(vec![Packet::Int(*n)]);
+        }
+        Packet::Int(n) => *n = rng.range(0..=10),
+    }
+}
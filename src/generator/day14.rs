@@ -0,0 +1,30 @@
+use crate::util::rng::Rng;
+
+/// Paths of rock made of horizontal and vertical lines, well below the source of the sand.
+/// Like in the real scans, some paths are listed more than once.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut paths: Vec<String> = Vec::new();
+    while paths.len() < 150 {
+        if !paths.is_empty() && rng.chance(0.3) {
+            let path = rng.choose(&paths).clone();
+            paths.push(path);
+            continue;
+        }
+        let (mut x, mut y) = (rng.range(470..=530), rng.range(13..=165));
+        let mut points = vec![format!("{x},{y}")];
+        let mut horizontal = rng.chance(0.5);
+        for _ in 0..rng.range(1..=5) {
+            let len = rng.range(1..=8) * if rng.chance(0.5) { 1 } else { -1 };
+            if horizontal {
+                x += len;
+            } else {
+                // Turn around rather than leave the scanned rows.
+                y += if (13..=170).contains(&(y + len)) { len } else { -len };
+            }
+            points.push(format!("{x},{y}"));
+            horizontal = !horizontal;
+        }
+        paths.push(points.join(" -> "));
+    }
+    paths.iter().map(|path| format!("{path}\n")).collect()
+}
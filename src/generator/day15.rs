@@ -0,0 +1,207 @@
+use crate::params::Day15;
+use crate::util::rng::Rng;
+
+type Point = (i64, i64);
+
+struct Sensor {
+    pos: Point,
+    range: i64,
+    beacon: Point,
+}
+
+fn dist(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Sensors that cover the whole search area of the default parameters,
+/// except for a single position where the distress beacon is.
+/// No beacon is in the range of a sensor other than its own, and each sensor has
+/// a single closest beacon, which some sensors share.
+pub fn generate(rng: &mut Rng) -> String {
+    let max = i64::from(Day15::default().max);
+    loop {
+        if let Some(input) = attempt(rng, max) {
+            return input;
+        }
+    }
+}
+
+fn attempt(rng: &mut Rng, max: i64) -> Option<String> {
+    let distress = (rng.range(0..=max), rng.range(0..=max));
+    let mut sensors = Vec::new();
+    // The edges of four sensors on the diagonals cross at the distress beacon,
+    // which leaves it as the only uncovered position around it.
+    let spread = max / 4;
+    for (sx, sy) in [(-1, -1), (1, -1), (1, 1), (-1, 1)] {
+        let (dx, dy) = (
+            rng.range(spread / 10..=spread),
+            rng.range(spread / 10..=spread),
+        );
+        let pos = (distress.0 + sx * dx, distress.1 + sy * dy);
+        place(rng, &mut sensors, distress, pos, dx + dy - 1, false)?;
+    }
+    for _ in 0..rng.range(16..=24) {
+        let pos = (
+            rng.range(-max / 10..=max * 11 / 10),
+            rng.range(-max / 10..=max * 11 / 10),
+        );
+        let shrink = rng.chance(0.5);
+        place(rng, &mut sensors, distress, pos, 1, shrink);
+    }
+    // Cover the remaining holes with sensors further away from the distress beacon.
+    for _ in 0..100 {
+        let Some(hole) = hole(&sensors, max, distress) else {
+            break;
+        };
+        let mut away = |a: i64, b: i64| match a.cmp(&b) {
+            std::cmp::Ordering::Equal => *rng.choose(&[-1, 1]),
+            ordering => ordering as i64,
+        };
+        let dir = (away(hole.0, distress.0), away(hole.1, distress.1));
+        let mut steps = spread / 2;
+        for _ in 0..20 {
+            let step = rng.range(1..=steps);
+            let pos = (hole.0 + dir.0 * step, hole.1 + dir.1 * step);
+            if place(rng, &mut sensors, distress, pos, 2 * step, false).is_some() {
+                break;
+            }
+            steps = (steps / 2).max(1);
+        }
+    }
+    if hole(&sensors, max, distress).is_some() {
+        return None;
+    }
+
+    let mut lines: Vec<String> = sensors
+        .iter()
+        .map(|Sensor { pos, beacon, .. }| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                pos.0, pos.1, beacon.0, beacon.1
+            )
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    Some(lines.concat())
+}
+
+/// Add a sensor at `pos` whose range reaches at least `min_range`, if possible.
+/// Its closest beacon is one of the known beacons, or otherwise a new one
+/// that no other sensor has in its range. The range of a new beacon may be `shrink`ed.
+fn place(
+    rng: &mut Rng,
+    sensors: &mut Vec<Sensor>,
+    distress: Point,
+    pos: Point,
+    min_range: i64,
+    shrink: bool,
+) -> Option<()> {
+    let limit = dist(pos, distress) - 1;
+    if limit < min_range.max(1) || sensors.iter().any(|s| s.pos == pos || s.beacon == pos) {
+        return None;
+    }
+    let mut beacons = sensors
+        .iter()
+        .map(|s| (dist(pos, s.beacon), s.beacon))
+        .collect::<Vec<_>>();
+    beacons.sort_unstable();
+    beacons.dedup();
+    let (range, beacon) = match beacons[..] {
+        [(range, beacon), ..] if range <= limit => {
+            // A beacon at the same distance as the closest one would be ambiguous.
+            if beacons.get(1).is_some_and(|&(next, _)| next == range) {
+                return None;
+            }
+            (range, beacon)
+        }
+        _ => {
+            let range = if shrink { limit - rng.range(0..=limit / 4) } else { limit };
+            let outside = |p: Point| sensors.iter().all(|o| dist(o.pos, p) > o.range);
+            let beacon = (0..100)
+                .map(|_| edge(rng, pos, range))
+                .find(|&p| outside(p))?;
+            (range, beacon)
+        }
+    };
+    if range < min_range {
+        return None;
+    }
+    sensors.push(Sensor { pos, range, beacon });
+    Some(())
+}
+
+/// A random position at the given distance from `pos`.
+fn edge(rng: &mut Rng, pos: Point, range: i64) -> Point {
+    let dx = rng.range(-range..=range);
+    let dy = (range - dx.abs()) * *rng.choose(&[-1, 1]);
+    (pos.0 + dx, pos.1 + dy)
+}
+
+/// A position in the search area that no sensor covers, other than the distress beacon.
+fn hole(sensors: &[Sensor], max: i64, distress: Point) -> Option<Point> {
+    // Rows where a range appears, starts shrinking or disappears.
+    let mut events: Vec<i64> = sensors
+        .iter()
+        .flat_map(|s| [s.pos.1 - s.range, s.pos.1, s.pos.1 + s.range + 1])
+        .collect();
+    events.sort_unstable();
+    let mut y = 0;
+    while y <= max {
+        // The covered range of each sensor in this row, and how its ends move towards the next row.
+        let mut ranges: Vec<((i64, i64), i64)> = sensors
+            .iter()
+            .filter_map(|s| {
+                let width = s.range - (s.pos.1 - y).abs();
+                let slope = if y < s.pos.1 { -1 } else { 1 };
+                (width >= 0).then_some(((s.pos.0 - width, s.pos.0 + width), slope))
+            })
+            .collect();
+        ranges.sort_unstable();
+        // Follow the chain of overlapping ranges along the row. Until the next event,
+        // the overlaps change linearly, so the chain covers the next rows as long as none of
+        // the overlaps shrinks below zero.
+        let mut rows = events.iter().find(|&&e| e > y).map_or(max + 1, |e| e - y);
+        let (mut x, mut end_slope) = (0, 0);
+        for ((start, end), slope) in ranges {
+            if x > max {
+                break;
+            }
+            if end < x {
+                continue;
+            }
+            if start > x {
+                if (x, y) != distress {
+                    return Some((x, y));
+                } else if start > x + 1 && x < max {
+                    return Some((x + 1, y));
+                }
+                x = start;
+                rows = 1;
+            }
+            let rate = if x == 0 { -slope } else { end_slope - slope };
+            rows = rows.min(overlapping(x - start, rate));
+            x = end + 1;
+            end_slope = -slope;
+        }
+        if x <= max {
+            if (x, y) != distress {
+                return Some((x, y));
+            } else if x < max {
+                return Some((x + 1, y));
+            }
+            rows = 1;
+        }
+        rows = rows.min(overlapping(x - 1 - max, end_slope));
+        y += rows.max(1);
+    }
+    None
+}
+
+/// For how many rows an overlap between ranges lasts, if it changes by `rate` per row.
+fn overlapping(margin: i64, rate: i64) -> i64 {
+    if rate < 0 {
+        margin / -rate
+    } else {
+        i64::MAX
+    }
+}
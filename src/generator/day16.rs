@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+
+use crate::util::rng::Rng;
+
+/// Fifteen valves with flow, and `AA` without, joined by corridors of one to three
+/// valves without flow. The flow rates are all different and the graph is connected.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut names = vec![String::from("AA")];
+    // Enough names for the longest corridors of all possible links.
+    while names.len() < 16 + 21 * 3 {
+        let name: String = (0..2).map(|_| char::from(rng.range(b'A'..=b'Z'))).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let mut names = names.into_iter();
+    let mut flows: Vec<u32> = (3..=25).collect();
+    rng.shuffle(&mut flows);
+    let mut valves: Vec<(String, u32)> = (0..16)
+        .map(|idx| (names.next().unwrap(), if idx == 0 { 0 } else { flows[idx] }))
+        .collect();
+
+    // A random tree connects all valves with flow, and a few more corridors add cycles.
+    let mut links = BTreeSet::new();
+    for valve in 1..16 {
+        links.insert((rng.index(valve), valve));
+    }
+    for _ in 0..rng.range(3..=6) {
+        let (a, b) = (rng.index(16), rng.index(16));
+        if a != b {
+            links.insert((a.min(b), a.max(b)));
+        }
+    }
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; 16];
+    for (a, b) in links {
+        let mut prev = a;
+        for _ in 0..rng.range(1..=3) {
+            let next = valves.len();
+            valves.push((names.next().unwrap(), 0));
+            tunnels.push(vec![prev]);
+            tunnels[prev].push(next);
+            prev = next;
+        }
+        tunnels[prev].push(b);
+        tunnels[b].push(prev);
+    }
+
+    let mut lines: Vec<String> = valves
+        .iter()
+        .zip(&tunnels)
+        .map(|((name, flow), tunnels)| {
+            let tunnels: Vec<&str> = tunnels.iter().map(|&t| valves[t].0.as_str()).collect();
+            let lead = if tunnels.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {name} has flow rate={flow}; {lead} {}\n",
+                tunnels.join(", ")
+            )
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
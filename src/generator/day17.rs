@@ -0,0 +1,9 @@
+use crate::util::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> String {
+    let mut jets: String = (0..rng.range(10_000..=10_100))
+        .map(|_| *rng.choose(&['<', '>']))
+        .collect();
+    jets.push('\n');
+    jets
+}
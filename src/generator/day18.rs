@@ -0,0 +1,26 @@
+use crate::util::rng::Rng;
+
+/// A lumpy droplet of a few thousand cubes within 0 to 21 in each direction.
+/// Some cubes inside are missing, which leaves pockets of trapped air.
+pub fn generate(rng: &mut Rng) -> String {
+    let center = [(); 3].map(|_| rng.range(100..=120) as f64 / 10.0);
+    let radius = rng.range(80..=100) as f64 / 10.0;
+    let mut lines = Vec::new();
+    for x in 0..=21 {
+        for y in 0..=21 {
+            for z in 0..=21 {
+                let offset = [x, y, z].map(f64::from);
+                let dist = (0..3)
+                    .map(|i| (offset[i] - center[i]).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                let lump = rng.range(-15..=15) as f64 / 10.0;
+                if dist + lump <= radius && rng.chance(0.9) {
+                    lines.push(format!("{x},{y},{z}\n"));
+                }
+            }
+        }
+    }
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
@@ -0,0 +1,19 @@
+use crate::util::rng::Rng;
+
+/// Thirty blueprints with costs in the same ranges as the real ones.
+pub fn generate(rng: &mut Rng) -> String {
+    (1..=30)
+        .map(|id| {
+            let (ore, clay) = (rng.range(2..=4), rng.range(2..=4));
+            let obsidian = (rng.range(2..=4), rng.range(5..=20));
+            let geode = (rng.range(2..=4), rng.range(7..=20));
+            format!(
+                "Blueprint {id}: Each ore robot costs {ore} ore. \
+                 Each clay robot costs {clay} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.\n",
+                obsidian.0, obsidian.1, geode.0, geode.1
+            )
+        })
+        .collect()
+}
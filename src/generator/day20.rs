@@ -0,0 +1,15 @@
+use crate::util::rng::Rng;
+
+/// Five thousand numbers, with duplicates but exactly one 0.
+pub fn generate(rng: &mut Rng) -> String {
+    let mut numbers: Vec<i32> = (0..4999)
+        .map(|_| loop {
+            let n = rng.range(-10_000..=10_000);
+            if n != 0 {
+                break n;
+            }
+        })
+        .collect();
+    numbers.insert(rng.index(numbers.len() + 1), 0);
+    numbers.iter().map(|n| format!("{n}\n")).collect()
+}
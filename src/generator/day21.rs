@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use crate::util::rng::Rng;
+
+/// Largest value a monkey yells, well within the exact integers of an `f64`.
+const LIMIT: i64 = 100_000_000_000_000;
+
+/// The common multiple of all divisors, which keeps divisions exact for both parts.
+const DIVISORS: i64 = 2520;
+
+/// A tree of monkeys with `root` adding up its two sides, and `humn` somewhere down one of them.
+/// Every division is exact, both with the number that `humn` yells in part 1
+/// and with the number that makes both sides of `root` equal in part 2.
+/// Nobody ever divides by a number that depends on `humn`, and all numbers fit in an `f64`.
+pub fn generate(rng: &mut Rng) -> String {
+    loop {
+        if let Some(input) = attempt(rng) {
+            return input;
+        }
+    }
+}
+
+fn attempt(rng: &mut Rng) -> Option<String> {
+    let mut monkeys = Monkeys { lines: vec![], names: HashSet::new() };
+    let humn = rng.range(100..=5000);
+    // The two numbers differ by a multiple of all divisors, so they stay congruent
+    // modulo any divisor on the way up, as long as the divisions keep enough factors.
+    let mut values = (humn, humn + DIVISORS * rng.range(100_000..=1_000_000_000));
+    monkeys.lines.push(format!("humn: {humn}"));
+    let mut side = String::from("humn");
+    for _ in 0..rng.range(40..=70) {
+        let diff = values.1 - values.0;
+        let divisors: Vec<i64> = (2..=10).filter(|d| diff % d == 0).collect();
+        let big = values.0.abs().max(values.1.abs()) > LIMIT / 20;
+        let n = rng.range(2..=10);
+        let choice = if big && !divisors.is_empty() { 4 } else { rng.range(0..=4) };
+        let (op, constant, swap) = match choice {
+            0 => ('+', rng.range(1..=1000), rng.chance(0.5)),
+            1 => ('-', rng.range(1..=1000), false),
+            2 => ('-', rng.range(0..=LIMIT / 100), true),
+            3 if !big => ('*', n, rng.chance(0.5)),
+            4 if !divisors.is_empty() => {
+                let d = *rng.choose(&divisors);
+                // Make the number divisible first.
+                let offset = (d - values.0.rem_euclid(d)) % d;
+                if offset != 0 {
+                    let constant = monkeys.constant(rng, offset);
+                    side = monkeys.binop(rng, &side, '+', &constant);
+                    values = (values.0 + offset, values.1 + offset);
+                }
+                ('/', d, false)
+            }
+            _ => ('+', rng.range(1..=1000), rng.chance(0.5)),
+        };
+        let apply = |x: i64| match (op, swap) {
+            ('+', _) => x + constant,
+            ('-', false) => x - constant,
+            ('-', true) => constant - x,
+            ('*', _) => x * constant,
+            _ => x / constant,
+        };
+        values = (apply(values.0), apply(values.1));
+        if values.0.abs().max(values.1.abs()) > LIMIT {
+            return None;
+        }
+        let constant = monkeys.constant(rng, constant);
+        side = if swap {
+            monkeys.binop(rng, &constant, op, &side)
+        } else {
+            monkeys.binop(rng, &side, op, &constant)
+        };
+    }
+    let other = monkeys.constant(rng, values.1);
+    let (left, right) = if rng.chance(0.5) { (side, other) } else { (other, side) };
+    monkeys.lines.push(format!("root: {left} + {right}"));
+    rng.shuffle(&mut monkeys.lines);
+    Some(
+        monkeys
+            .lines
+            .iter()
+            .map(|line| format!("{line}\n"))
+            .collect(),
+    )
+}
+
+struct Monkeys {
+    lines: Vec<String>,
+    names: HashSet<String>,
+}
+
+impl Monkeys {
+    fn name(&mut self, rng: &mut Rng) -> String {
+        loop {
+            let name: String = (0..4).map(|_| char::from(rng.range(b'a'..=b'z'))).collect();
+            if !["root", "humn"].contains(&name.as_str()) && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn binop(&mut self, rng: &mut Rng, a: &str, op: char, b: &str) -> String {
+        let name = self.name(rng);
+        self.lines.push(format!("{name}: {a} {op} {b}"));
+        name
+    }
+
+    /// Monkeys that work out the given number without `humn`, returning the one that yells it.
+    /// Large numbers are broken down into a digit in some base, plus a smaller number
+    /// times the base, so that the monkeys only yell small numbers themselves.
+    fn constant(&mut self, rng: &mut Rng, value: i64) -> String {
+        if (0..=20).contains(&value) && rng.chance(0.8) || value == 0 {
+            let name = self.name(rng);
+            self.lines.push(format!("{name}: {value}"));
+            return name;
+        }
+        if value < 0 {
+            let small = rng.range(0..=20);
+            let a = self.constant(rng, small);
+            let b = self.constant(rng, small - value);
+            return self.binop(rng, &a, '-', &b);
+        }
+        if rng.chance(0.1) && value < LIMIT / 10 {
+            let d = rng.range(2..=10);
+            let a = self.constant(rng, value * d);
+            let b = self.constant(rng, d);
+            return self.binop(rng, &a, '/', &b);
+        }
+        let base = rng.range(2..=20);
+        let (quotient, rest) = (value / base, value % base);
+        let (quotient, rest, op) = if rest != 0 && rng.chance(0.5) {
+            (quotient + 1, base - rest, '-')
+        } else {
+            (quotient, rest, '+')
+        };
+        let scaled = if quotient == 1 {
+            self.constant(rng, base)
+        } else {
+            let a = self.constant(rng, quotient);
+            let b = self.constant(rng, base);
+            if rng.chance(0.5) {
+                self.binop(rng, &a, '*', &b)
+            } else {
+                self.binop(rng, &b, '*', &a)
+            }
+        };
+        if rest == 0 {
+            return scaled;
+        }
+        let rest = self.constant(rng, rest);
+        self.binop(rng, &scaled, op, &rest)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::params::Day22;
+use crate::util::rng::Rng;
+
+type Vector = [i8; 3];
+
+fn neg(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+/// The orientation of a face of the cube: the direction it faces,
+/// and the directions of going right and down on the map.
+#[derive(Clone, Copy)]
+struct Face {
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    /// The orientation of the face next to this one on the map,
+    /// in a direction given as a step on the map.
+    fn roll(self, (dx, dy): (isize, isize)) -> Self {
+        let Face { normal, right, down } = self;
+        match (dx, dy) {
+            (1, 0) => Face { normal: right, right: neg(normal), down },
+            (-1, 0) => Face { normal: neg(right), right: normal, down },
+            (0, 1) => Face { normal: down, right, down: neg(normal) },
+            _ => Face { normal: neg(down), right, down: normal },
+        }
+    }
+}
+
+/// A random net of a cube with the default size of faces, with a few walls,
+/// followed by a path that alternates between moving and turning.
+pub fn generate(rng: &mut Rng) -> String {
+    let size = Day22::default().cube_size;
+    let faces = net(rng);
+    let rows = faces.iter().map(|&(_, y)| y).max().unwrap() + 1;
+    let mut out = String::new();
+    // The path starts on the leftmost tile of the top row, which has to be open.
+    let first = faces
+        .iter()
+        .filter(|&&(_, y)| y == 0)
+        .map(|&(x, _)| x)
+        .min()
+        .unwrap();
+    for row in 0..rows {
+        let right = faces
+            .iter()
+            .filter(|&&(_, y)| y == row)
+            .map(|&(x, _)| x)
+            .max()
+            .unwrap();
+        for y in 0..size {
+            for column in 0..=right {
+                let face = faces.contains(&(column, row));
+                for x in 0..size {
+                    let start = (column, row, x, y) == (first, 0, 0, 0);
+                    out.push(match face {
+                        false => ' ',
+                        true if !start && rng.chance(0.1) => '#',
+                        true => '.',
+                    });
+                }
+            }
+            out.push('\n');
+        }
+    }
+    out.push('\n');
+    out += &rng.range(1..=size).to_string();
+    for _ in 0..rng.range(1800..=2200) {
+        out.push(*rng.choose(&['L', 'R']));
+        out += &rng.range(1..=size).to_string();
+    }
+    out + "\n"
+}
+
+/// Positions of the faces of a random net, in units of faces.
+fn net(rng: &mut Rng) -> Vec<(usize, usize)> {
+    loop {
+        let first = Face { normal: [0, 0, 1], right: [1, 0, 0], down: [0, 1, 0] };
+        let mut faces: Vec<((isize, isize), Face)> = vec![((0, 0), first)];
+        for _ in 0..100 {
+            let (pos, face) = *rng.choose(&faces);
+            let step = *rng.choose(&[(1, 0), (-1, 0), (0, 1), (0, -1)]);
+            let next = (pos.0 + step.0, pos.1 + step.1);
+            let rolled = face.roll(step);
+            let taken = faces
+                .iter()
+                .any(|(p, f)| *p == next || f.normal == rolled.normal);
+            if !taken {
+                faces.push((next, rolled));
+                if faces.len() == 6 {
+                    break;
+                }
+            }
+        }
+        if faces.len() < 6 {
+            continue;
+        }
+        let left = faces.iter().map(|((x, _), _)| *x).min().unwrap();
+        let top = faces.iter().map(|((_, y), _)| *y).min().unwrap();
+        return faces
+            .iter()
+            .map(|((x, y), _)| ((x - left) as usize, (y - top) as usize))
+            .collect();
+    }
+}
@@ -0,0 +1,12 @@
+use crate::util::rng::Rng;
+
+/// A square of about 70 by 70 positions, about half of them with an elf.
+pub fn generate(rng: &mut Rng) -> String {
+    let size = rng.range(66..=76);
+    let mut out = String::new();
+    for _ in 0..size {
+        out.extend((0..size).map(|_| if rng.chance(0.5) { '#' } else { '.' }));
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,25 @@
+use crate::util::rng::Rng;
+
+/// A valley of about 120 by 25 positions, with the entrance in the top left corner
+/// and the exit in the bottom right one. Blizzards fill about two thirds of the valley,
+/// but none of them moves up or down in the columns of the entrance and the exit,
+/// so that they never leave the valley.
+pub fn generate(rng: &mut Rng) -> String {
+    let (width, height) = (rng.range(100..=120), rng.range(20..=25));
+    let wall = |gap: usize| {
+        let mut row = vec!['#'; width + 2];
+        row[gap] = '.';
+        row.into_iter().collect::<String>() + "\n"
+    };
+    let mut out = wall(1);
+    for _ in 0..height {
+        out.push('#');
+        for x in 0..width {
+            let blizzards: &[char] =
+                if x == 0 || x == width - 1 { &['<', '>'] } else { &['<', '>', '^', 'v'] };
+            out.push(if rng.chance(0.65) { *rng.choose(blizzards) } else { '.' });
+        }
+        out.push_str("#\n");
+    }
+    out + &wall(width)
+}
@@ -0,0 +1,14 @@
+use crate::util::rng::Rng;
+
+/// SNAFU numbers of up to 20 digits, which start with 1 or 2 so that they are positive.
+pub fn generate(rng: &mut Rng) -> String {
+    (0..rng.range(100..=130))
+        .map(|_| {
+            let mut number = String::from(*rng.choose(&["1", "2"]));
+            for _ in 1..rng.range(1..=20) {
+                number.push(*rng.choose(&['=', '-', '0', '1', '2']));
+            }
+            number + "\n"
+        })
+        .collect()
+}
@@ -1,3 +1,11 @@
 pub mod util;
+pub mod answer;
+pub mod ocr;
 pub mod solutions;
 pub mod optimized;
+pub mod profile;
+pub mod registry;
+pub mod formats;
+pub mod assumptions;
+pub mod generator;
+pub mod params;
@@ -1,125 +1,710 @@
-use std::fmt::Display;
-use std::fs::read_to_string;
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::any::Any;
+use std::path::Path;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
-use comfy_table::{presets::UTF8_FULL_CONDENSED, Cell, CellAlignment, Table};
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Cell, CellAlignment, Color, Table};
 
-mod optimized;
-#[allow(dead_code)]
-mod solutions;
-pub mod util;
+use aoc2022::answer::Answer;
+use aoc2022::assumptions::Broken;
+use aoc2022::formats;
+use aoc2022::generator;
+use aoc2022::params::{self, Override};
+use aoc2022::profile::{Expected, Profile};
+use aoc2022::registry::{self, Solution, Visitor};
+use args::{Args, Command, DAYS, USAGE};
+use output::{Failure, Format, Record, Solved};
+use snapshot::Snapshot;
+use timing::{Repeat, Stats};
+
+mod args;
+mod fuzz;
+mod output;
+mod remote;
+mod scaffold;
+mod snapshot;
+mod timing;
+mod watch;
 
 fn main() {
-    let dir = std::fs::read_dir("data").expect("no data directory");
-    let mut profiles: Vec<_> = dir.map(|x| x.unwrap()).collect();
-    profiles.sort_by_key(|profile| profile.path());
-    for entry in profiles {
-        let mut path = entry.path();
-        println!("Profile: {}", path.file_name().unwrap().to_str().unwrap());
-        path.push("inputs");
-        let mut state = State::new(path);
-        state.run_day(1, &optimized::day01::run);
-        state.run_day(2, &solutions::day02::run);
-        state.run_day(3, &solutions::day03::run);
-        state.run_day(4, &solutions::day04::run);
-        state.run_day(5, &solutions::day05::run);
-        state.run_day(6, &optimized::day06::run);
-        state.run_day(7, &solutions::day07::run);
-        state.run_day(8, &solutions::day08::run);
-        state.run_day(9, &solutions::day09::run);
-        state.run_day(10, &solutions::day10::run);
-        state.run_day(11, &optimized::day11::run);
-        state.run_day(12, &solutions::day12::run);
-        state.run_day(13, &optimized::day13::run);
-        state.run_day(14, &optimized::day14::run);
-        state.run_day(15, &optimized::day15::run);
-        state.run_day(16, &optimized::day16::run);
-        state.run_day(17, &solutions::day17::run);
-        state.run_day(18, &solutions::day18::run);
-        state.run_day(19, &optimized::day19::run);
-        state.run_day(20, &solutions::day20::run);
-        state.run_day(21, &solutions::day21::run);
-        state.run_day(22, &solutions::day22::run);
-        state.run_day(23, &optimized::day23::run);
-        state.run_day(24, &optimized::day24::run);
-        state.run_day(25, &solutions::day25::run);
-        state.print();
-    }
-}
-
-struct State {
-    input_folder: PathBuf,
+    let args = Args::parse(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("error: {err}\n\n{USAGE}");
+        std::process::exit(2);
+    });
+    if args.help {
+        println!("{USAGE}");
+        return;
+    }
+    if let Command::NewDay(day) = args.command {
+        if let Err(err) = scaffold::new_day(day, args.optimized, args.first) {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Command::Generate(seed) = args.command {
+        generate(&args, seed.unwrap_or(0));
+        return;
+    }
+
+    let mut check = CheckParams { overrides: &args.params, errors: Vec::new() };
+    registry::visit_all(&mut check);
+    if let Some(err) = check.errors.first() {
+        eprintln!("error: {err}");
+        std::process::exit(2);
+    }
+    if let Command::Fuzz(runs) = args.command {
+        match std::env::var(fuzz::JOB) {
+            Ok(job) => fuzz::child(&args, &job),
+            Err(_) => fuzz::fuzz(&args, runs.unwrap_or(1000)),
+        }
+        return;
+    }
+
+    let mut profiles = Profile::all().expect("no data directory");
+    for name in args.profiles() {
+        if profiles.iter().any(|profile| &profile.name == name) {
+            continue;
+        }
+        // Profiles from fuzzing are only used when selected.
+        let Some(profile) = Profile::named(name) else {
+            eprintln!("error: no profile named `{name}` in data/");
+            std::process::exit(2);
+        };
+        profiles.push(profile);
+    }
+    let profiles: Vec<&Profile> =
+        profiles.iter().filter(|profile| args.profile(&profile.name)).collect();
+    match &args.command {
+        Command::Run => run(&args, &profiles),
+        Command::Fetch => fetch(&args, &profiles),
+        Command::Submit(answer) => submit(&args, &profiles, answer.as_deref()),
+        Command::Watch => {
+            let days: Vec<usize> = DAYS.filter(|&day| args.day(day)).collect();
+            let [day] = days[..] else {
+                eprintln!("error: `watch` needs a single day\n\n{USAGE}");
+                std::process::exit(2);
+            };
+            watch::watch(&args, &profiles, day)
+        }
+        Command::NewDay(_) | Command::Generate(_) | Command::Fuzz(_) => {
+            unreachable!("doesn't need the profiles, so handled above")
+        }
+    }
+}
+
+/// Write generated inputs of the selected days to the profile of the seed.
+fn generate(args: &Args, seed: u64) {
+    let name = format!("seed-{seed}");
+    let profile = Profile { path: Path::new("data").join(&name), name };
+    for day in DAYS.filter(|&day| args.day(day)) {
+        let path = profile.input_path(day);
+        let written = std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| std::fs::write(&path, generator::generate(day, seed)));
+        if let Err(err) = written {
+            eprintln!("error: couldn't write `{}`: {err}", path.display());
+            std::process::exit(1);
+        }
+        println!("wrote {}", path.display());
+    }
+}
+
+/// Run the selected days of the selected profiles, printing tables or another output format.
+fn run(args: &Args, profiles: &[&Profile]) {
+    let baseline = args.compare.as_deref().map(|path| {
+        Snapshot::load(path).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            std::process::exit(2);
+        })
+    });
+    let mut jobs = Jobs { overrides: &args.params, jobs: Vec::new() };
+    registry::visit_all(&mut jobs);
+    let groups = groups(args, &jobs.jobs);
+    let records = match args.jobs {
+        Some(threads) => run_parallel(args, profiles, baseline.as_ref(), &groups, threads),
+        None => {
+            let mut records = Vec::new();
+            for profile in profiles {
+                let mut state = State::new(profile, args, baseline.as_ref());
+                for group in &groups {
+                    state.add_tried(solve_first(profile, args.repeat, group));
+                }
+                records.extend(state.print());
+            }
+            records
+        }
+    };
+    if args.format != Format::Table {
+        print!("{}", output::render(args.format, &records, args.parts()));
+    }
+    if let Some(path) = &args.save {
+        if let Err(err) = Snapshot::from_records(&records).save(path) {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+    }
+
+    // Exit with an error if any selected answer didn't match its solution file,
+    // or if an input couldn't be solved. Missing inputs are fine,
+    // and so are inputs that only some of the implementations of a day support.
+    let solved = |record: &Record| {
+        records.iter().any(|other| {
+            other.profile == record.profile && other.day == record.day && other.outcome.is_ok()
+        })
+    };
+    let wrong = records.iter().any(|record| {
+        (0..2).any(|part| args.parts()[part] && record.correct(part) == Some(false))
+            || matches!(record.outcome, Err(Failure::Parse(_) | Failure::Panic(_)))
+            || matches!(record.outcome, Err(Failure::Unsupported(_))) && !solved(record)
+    });
+    if wrong {
+        std::process::exit(1);
+    }
+}
+
+fn client(args: &Args, profile: &Profile) -> remote::Client {
+    let base_url = args.base_url.clone().or_else(|| std::env::var("AOC_BASE_URL").ok());
+    let base_url = base_url.as_deref().unwrap_or(remote::DEFAULT_BASE_URL);
+    remote::Client::new(profile, base_url, args.throttle).unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    })
+}
+
+/// Download the missing inputs of the selected days and profiles.
+fn fetch(args: &Args, profiles: &[&Profile]) {
+    for &profile in profiles {
+        let days = DAYS.filter(|&day| args.day(day));
+        if let Err(err) = remote::fetch(&client(args, profile), profile, days) {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Submit an answer to the selected day and part, running the solution if none is given.
+fn submit(args: &Args, profiles: &[&Profile], answer: Option<&str>) {
+    let days: Vec<usize> = DAYS.filter(|&day| args.day(day)).collect();
+    let parts: Vec<usize> = (0..2).filter(|&part| args.parts()[part]).collect();
+    let (&[day], &[part], &[profile]) = (&days[..], &parts[..], profiles) else {
+        eprintln!("error: `submit` needs a single day, part and profile\n\n{USAGE}");
+        std::process::exit(2);
+    };
+    let answer = match answer {
+        Some(answer) => answer.to_owned(),
+        None => {
+            let mut jobs = Jobs { overrides: &args.params, jobs: Vec::new() };
+            registry::visit_all(&mut jobs);
+            let jobs: Vec<&Job> = jobs.jobs.iter().filter(|job| job.day == day).collect();
+            let (_, result) = solve_first(profile, Repeat::default(), &jobs).pop().unwrap();
+            match result {
+                Ok((answers, _)) => match answers[part].text() {
+                    Ok(text) => text,
+                    Err(err) => {
+                        eprintln!("error: couldn't read the answer: {err}\n{}", answers[part]);
+                        std::process::exit(1);
+                    }
+                },
+                Err(failure) => {
+                    eprintln!("error: {failure}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+    if answer.trim_end().contains('\n') {
+        eprintln!("error: can't submit a multi-line answer, pass the text it shows instead");
+        std::process::exit(2);
+    }
+
+    match remote::submit(&client(args, profile), profile, day, part, &answer) {
+        Ok(verdict) => {
+            println!("Day {day}, part {}: {} is {verdict}", part + 1, answer.trim());
+            if verdict != remote::Verdict::Correct {
+                std::process::exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+struct State<'a> {
+    args: &'a Args,
+    profile: &'a Profile,
+    records: Vec<Record>,
     table: Table,
+    /// Time spent in the first implementation run for each day,
+    /// using the median when timing multiple runs.
     total: Duration,
+    /// Times of a previous run to compare against.
+    baseline: Option<&'a Snapshot>,
+    /// Time spent in the same days as `total` in the baseline,
+    /// unless some of them are missing from it.
+    baseline_total: Option<Duration>,
+    regressions: Vec<String>,
     large_answers: Vec<String>,
+    wrong_answers: Vec<String>,
+    failures: Vec<String>,
+    /// Implementations used because the chosen one doesn't support the input.
+    fallbacks: Vec<String>,
+    /// Answers of the first implementation run for the current day.
+    reference: Option<(usize, &'static str, [Answer; 2])>,
+    disagreements: Vec<String>,
 }
 
-impl State {
-    fn new(input_folder: PathBuf) -> Self {
+impl<'a> State<'a> {
+    fn new(profile: &'a Profile, args: &'a Args, baseline: Option<&'a Snapshot>) -> Self {
         let mut table = Table::new();
         table.load_preset(UTF8_FULL_CONDENSED);
-        table.set_header(vec!["Day", "Part 1", "Part 2", "Time"]);
-        Self { input_folder, table, total: Duration::ZERO, large_answers: Vec::new() }
-    }
-
-    fn run_day<A1: Display, A2: Display>(
-        &mut self,
-        day: usize,
-        task: &dyn Fn(&str) -> (A1, A2),
-    ) -> (A1, A2) {
-        let mut path = self.input_folder.clone();
-        path.push(format!("day{day:02}.txt"));
-        // Read input
-        let input = read_to_string(path)
-            .unwrap_or_else(|_| panic!("missing input: {}", self.input_folder.display()));
-
-        // Run solution
-        let start = Instant::now();
-        let (res1, res2) = task(input.as_str());
-        let end = Instant::now();
-        let delta = end.duration_since(start);
-
-        // Check for long answers
-        let mut res1_str = res1.to_string();
-        if res1_str.lines().count() > 1 {
-            self.large_answers
-                .push(format!("Day {day:.2}, part 1:\n{res1_str}"));
-            res1_str = String::from("see below");
-        }
-
-        let mut res2_str = res2.to_string();
-        if res2_str.lines().count() > 1 {
-            self.large_answers
-                .push(format!("Day {day:.2}, part 2:\n{res2_str}"));
-            res2_str = String::from("see below");
-        }
-
-        // Add answers to table
-        self.table.add_row(vec![
+        let parts = ["Part 1", "Part 2"];
+        let times = if args.repeat.is_statistical() {
+            vec!["Min", "Median", "Mean", "Std dev", "Runs"]
+        } else {
+            vec!["Time"]
+        };
+        // Times measured in parallel are marked with an asterisk
+        let unreliable = if args.jobs.is_some() { "*" } else { "" };
+        let mark = |time: &str| match time {
+            "Runs" => time.to_owned(),
+            _ => format!("{time}{unreliable}"),
+        };
+        let mut times: Vec<String> = times.into_iter().map(mark).collect();
+        if baseline.is_some() {
+            times.push(format!("Change{unreliable}"));
+        }
+        let header = Self::columns(args, "Day", "Implementation", parts, Vec::new());
+        table.set_header(header.into_iter().map(String::from).chain(times));
+        Self {
+            args,
+            profile,
+            records: Vec::new(),
+            table,
+            total: Duration::ZERO,
+            baseline,
+            baseline_total: baseline.map(|_| Duration::ZERO),
+            regressions: Vec::new(),
+            large_answers: Vec::new(),
+            wrong_answers: Vec::new(),
+            failures: Vec::new(),
+            fallbacks: Vec::new(),
+            reference: None,
+            disagreements: Vec::new(),
+        }
+    }
+
+    /// Build a row, leaving out the columns that aren't shown.
+    fn columns<T>(args: &Args, day: T, name: T, parts: [T; 2], times: Vec<T>) -> Vec<T> {
+        let mut row = vec![day];
+        if args.cross_check {
+            row.push(name);
+        }
+        row.extend(args.parts().into_iter().zip(parts).filter(|(s, _)| *s).map(|(_, p)| p));
+        row.extend(times);
+        row
+    }
+
+    /// Number of timing columns, including the change from the baseline.
+    fn time_columns(&self) -> usize {
+        let times = if self.args.repeat.is_statistical() { 5 } else { 1 };
+        times + self.baseline.is_some() as usize
+    }
+
+    /// Cell with the change in time from the baseline, flagging regressions.
+    fn change_cell(&mut self, what: &str, old: Option<Duration>, new: Duration) -> Cell {
+        let Some((old, change)) = old.and_then(|old| Some((old, snapshot::change(old, new)?)))
+        else {
+            return Cell::new("?").set_alignment(CellAlignment::Right);
+        };
+        let color = if change > self.args.threshold {
+            self.regressions.push(format!("{what}: {old:.2?} -> {new:.2?} ({change:+.1}%)"));
+            Color::Red
+        } else if change < -self.args.threshold {
+            Color::Green
+        } else {
+            Color::Reset
+        };
+        Cell::new(format!("{change:+.1}%")).set_alignment(CellAlignment::Right).fg(color)
+    }
+
+    /// Cells for the timing columns.
+    fn time_cells(&self, stats: &Stats) -> Vec<Cell> {
+        let cell = |s: String| Cell::new(s).set_alignment(CellAlignment::Right);
+        if self.args.repeat.is_statistical() {
+            vec![
+                cell(format!("{:.2?}", stats.min)),
+                cell(format!("{:.2?}", stats.median)),
+                cell(format!("{:.2?}", stats.mean)),
+                cell(format!("{:.2?}", stats.std_dev)),
+                cell(stats.runs.to_string()),
+            ]
+        } else {
+            vec![cell(format!("{:.2?}", stats.median))]
+        }
+    }
+
+    /// Add a row for an implementation that couldn't be run.
+    fn fail(&mut self, day: usize, name: &'static str, expected: Expected, failure: Failure) {
+        let color = match failure {
+            Failure::Missing(_) => Color::DarkGrey,
+            Failure::Parse(_) | Failure::Panic(_) => Color::Red,
+            Failure::Unsupported(_) => Color::Yellow,
+        };
+        let cell = || Cell::new(failure.label()).set_alignment(CellAlignment::Right).fg(color);
+        let times = (0..self.time_columns()).map(|_| Cell::new("")).collect();
+        self.table.add_row(Self::columns(
+            self.args,
             Cell::new(day.to_string()).set_alignment(CellAlignment::Right),
-            Cell::new(res1_str).set_alignment(CellAlignment::Right),
-            Cell::new(res2_str).set_alignment(CellAlignment::Right),
-            Cell::new(format!("{delta:.2?}")).set_alignment(CellAlignment::Right),
-        ]);
-        self.total += delta;
+            Cell::new(name),
+            [cell(), cell()],
+            times,
+        ));
+        // Every day of a profile may be missing, so only list the other failures
+        if !matches!(failure, Failure::Missing(_)) {
+            self.failures.push(format!("Day {day:.2}, {name}: {failure}"));
+        }
+        self.records.push(Record {
+            profile: self.profile.name.clone(),
+            day,
+            implementation: name,
+            expected,
+            outcome: Err(failure),
+        });
+    }
+
+    /// Add the results of the implementations tried for a day, see [`solve_first`].
+    /// The ones followed by another didn't support the input, so they are only mentioned.
+    fn add_tried(&mut self, tried: Vec<(&Job, Solve)>) {
+        let mut tried = tried.into_iter().peekable();
+        while let Some((job, result)) = tried.next() {
+            match (result, tried.peek()) {
+                (Err(Failure::Unsupported(broken)), Some((next, _))) => {
+                    for broken in broken {
+                        let (day, name) = (job.day, next.name);
+                        self.fallbacks.push(format!("Day {day:.2}, {name}: used because {broken}"));
+                    }
+                }
+                (result, _) => self.add(job.day, job.name, result),
+            }
+        }
+    }
+
+    /// Add the result of running an implementation for a day.
+    fn add(&mut self, day: usize, name: &'static str, result: Solve) {
+        let expected = self.profile.expected(day).unwrap_or_default();
+        let (answers, stats) = match result {
+            Ok(solved) => solved,
+            Err(failure) => return self.fail(day, name, expected, failure),
+        };
+
+        // Compare against the first implementation of the same day
+        let mut disagrees = [false; 2];
+        match &self.reference {
+            Some((ref_day, ref_name, ref_answers)) if *ref_day == day => {
+                for part in 0..2 {
+                    if !answers[part].agrees(&ref_answers[part]) {
+                        disagrees[part] = true;
+                        self.disagreements.push(format!(
+                            "Day {day:.2}, part {}: {name} answered {}, {ref_name} answered {}",
+                            part + 1,
+                            answers[part],
+                            ref_answers[part],
+                        ));
+                    }
+                }
+            }
+            _ => {
+                self.reference = Some((day, name, answers.clone()));
+                self.total += stats.median;
+                if let Some(baseline) = self.baseline {
+                    let old = baseline.get(&self.profile.name, day, name);
+                    self.baseline_total = self.baseline_total.zip(old).map(|(t, old)| t + old);
+                }
+            }
+        }
 
-        (res1, res2)
+        // Add answers to table, marking them as correct (✓), wrong (✗) or unknown (?),
+        // and moving long answers below it.
+        // Pictures are shown as the letters they draw, if they can be read.
+        let [cell1, cell2] = [0, 1].map(|part| {
+            if !self.args.parts()[part] {
+                return Cell::new("");
+            }
+            let mut res = answers[part].text().unwrap_or_else(|_| answers[part].to_string());
+            let correct = expected.check(part, &answers[part]);
+            if correct == Some(false) {
+                let expected = expected.0[part].as_ref().unwrap();
+                self.wrong_answers.push(format!(
+                    "Day {day:.2}, {name}, part {}: expected {expected}, got {res}",
+                    part + 1
+                ));
+            }
+            if res.contains('\n') {
+                self.large_answers
+                    .push(format!("Day {day:.2}, {name}, part {}:\n{res}", part + 1));
+                res = String::from("see below");
+            }
+            let (mark, color) = match correct {
+                Some(true) => ('✓', Color::Green),
+                Some(false) => ('✗', Color::Red),
+                None => ('?', Color::Reset),
+            };
+            let cell = Cell::new(format!("{res} {mark}")).set_alignment(CellAlignment::Right);
+            if disagrees[part] {
+                cell.fg(Color::Red)
+            } else {
+                cell.fg(color)
+            }
+        });
+        let mut times = self.time_cells(&stats);
+        if let Some(baseline) = self.baseline {
+            let old = baseline.get(&self.profile.name, day, name);
+            times.push(self.change_cell(&format!("Day {day:.2}, {name}"), old, stats.median));
+        }
+        self.table.add_row(Self::columns(
+            self.args,
+            Cell::new(day.to_string()).set_alignment(CellAlignment::Right),
+            Cell::new(name),
+            [cell1, cell2],
+            times,
+        ));
+        self.records.push(Record {
+            profile: self.profile.name.clone(),
+            day,
+            implementation: name,
+            expected,
+            outcome: Ok(Solved { answers, stats, parallel: self.args.jobs.is_some() }),
+        });
     }
 
-    fn print(mut self) {
-        self.table.add_row(vec!["", "", "", ""]);
-        self.table.add_row(vec![
+    /// Print the table for this profile, unless another output format has been chosen.
+    fn print(mut self) -> Vec<Record> {
+        if self.args.format != Format::Table {
+            for disagreement in &self.disagreements {
+                eprintln!("Disagreement in {}! {disagreement}", self.profile.name);
+            }
+            for wrong in &self.wrong_answers {
+                eprintln!("Wrong answer in {}! {wrong}", self.profile.name);
+            }
+            for failure in &self.failures {
+                eprintln!("Failure in {}! {failure}", self.profile.name);
+            }
+            for fallback in &self.fallbacks {
+                eprintln!("Fallback in {}! {fallback}", self.profile.name);
+            }
+            for regression in &self.regressions {
+                eprintln!("Regression in {}! {regression}", self.profile.name);
+            }
+            return self.records;
+        }
+        println!("Profile: {}", self.profile.name);
+        let empty = || Cell::new("");
+        // The total goes in the median column when timing multiple runs
+        let time_columns = self.time_columns();
+        let median = if self.args.repeat.is_statistical() { 1 } else { 0 };
+        let times = (0..time_columns).map(|_| empty()).collect();
+        self.table.add_row(Self::columns(self.args, empty(), empty(), [empty(), empty()], times));
+        let mut times: Vec<Cell> = (0..time_columns).map(|_| empty()).collect();
+        times[median] =
+            Cell::new(format!("{:.2?}", self.total)).set_alignment(CellAlignment::Right);
+        if self.baseline.is_some() {
+            times[time_columns - 1] = self.change_cell("Total", self.baseline_total, self.total);
+        }
+        self.table.add_row(Self::columns(
+            self.args,
             Cell::new("Total"),
-            Cell::new(""),
-            Cell::new(""),
-            Cell::new(format!("{:.2?}", self.total)).set_alignment(CellAlignment::Right),
-        ]);
-        println!("{}\n", self.table);
+            empty(),
+            [empty(), empty()],
+            times,
+        ));
+        println!("{}", self.table);
+        if self.args.jobs.is_some() {
+            println!("* Measured while running in parallel, so not suitable for benchmarking.");
+        }
+        println!();
 
         // print long solutions after table
         for answer in self.large_answers {
             println!("{answer}\n");
         }
+
+        for disagreement in &self.disagreements {
+            println!("Disagreement! {disagreement}");
+        }
+        for wrong in &self.wrong_answers {
+            println!("Wrong answer! {wrong}");
+        }
+        for failure in &self.failures {
+            println!("Failure! {failure}");
+        }
+        for fallback in &self.fallbacks {
+            println!("Fallback! {fallback}");
+        }
+        for regression in &self.regressions {
+            println!("Regression! {regression}");
+        }
+        if !self.disagreements.is_empty()
+            || !self.wrong_answers.is_empty()
+            || !self.failures.is_empty()
+            || !self.fallbacks.is_empty()
+            || !self.regressions.is_empty()
+        {
+            println!();
+        }
+        self.records
+    }
+}
+
+/// Running an implementation of a day with its parameters on an input.
+type Task<'a> = dyn Fn(&str) -> [Answer; 2] + Sync + 'a;
+
+/// Answers and timings of running an implementation, or why it couldn't be run.
+type Solve = Result<([Answer; 2], Stats), Failure>;
+
+/// Read the input of a day, check its format and the assumptions of an implementation,
+/// and run the implementation on it.
+fn solve(profile: &Profile, repeat: Repeat, job: &Job) -> Solve {
+    let day = job.day;
+    let input = profile.input(day).map_err(|_| Failure::Missing(profile.input_path(day)))?;
+    // The format and the assumptions are checked outside of the timed section.
+    formats::check(day, &input).map_err(Failure::Parse)?;
+    let broken = (job.validate)(&input);
+    if !broken.is_empty() {
+        return Err(Failure::Unsupported(broken));
+    }
+    // Anything the format check doesn't catch shouldn't stop the remaining days.
+    let measure = || timing::measure(repeat, || (job.run)(input.as_str()));
+    panic::catch_unwind(AssertUnwindSafe(measure))
+        .map_err(|payload| Failure::Panic(panic_message(payload.as_ref())))
+}
+
+/// Run the first of the given implementations of a day that supports the input,
+/// returning the results of all the implementations tried.
+fn solve_first<'j>(profile: &Profile, repeat: Repeat, jobs: &[&'j Job]) -> Vec<(&'j Job, Solve)> {
+    let mut tried = Vec::new();
+    for &job in jobs {
+        let result = solve(profile, repeat, job);
+        let unsupported = matches!(result, Err(Failure::Unsupported(_)));
+        tried.push((job, result));
+        if !unsupported {
+            break;
+        }
+    }
+    tried
+}
+
+/// Implementations of the selected days to try on each input with [`solve_first`]:
+/// every implementation on its own when cross-checking,
+/// otherwise all implementations of a day together, so that the first one is used if possible.
+fn groups<'j>(args: &Args, jobs: &'j [Job]) -> Vec<Vec<&'j Job>> {
+    let days = jobs.chunk_by(|a, b| a.day == b.day).filter(|jobs| args.day(jobs[0].day));
+    if args.cross_check {
+        days.flatten().map(|job| vec![job]).collect()
+    } else {
+        days.map(|jobs| jobs.iter().collect()).collect()
+    }
+}
+
+/// Parameters of a day with the overrides from the command line,
+/// which have been checked by [`CheckParams`].
+fn day_params<S: Solution>(overrides: &[Override]) -> S::Params {
+    params::resolve(S::DAY, overrides).expect("checked overrides")
+}
+
+/// Checks that every override names a parameter of its day.
+struct CheckParams<'a> {
+    overrides: &'a [Override],
+    errors: Vec<String>,
+}
+
+impl Visitor for CheckParams<'_> {
+    fn visit<S: Solution>(&mut self) {
+        if let Err(err) = params::resolve::<S::Params>(S::DAY, self.overrides) {
+            self.errors.push(err);
+        }
+    }
+}
+
+/// An implementation of a day with its parameters,
+/// with the answers converted so that implementations of all days fit in one list.
+struct Job {
+    day: usize,
+    name: &'static str,
+    run: Box<Task<'static>>,
+    /// Assumptions of the implementation that an input breaks.
+    validate: fn(&str) -> Vec<Broken>,
+}
+
+struct Jobs<'a> {
+    overrides: &'a [Override],
+    jobs: Vec<Job>,
+}
+
+impl Visitor for Jobs<'_> {
+    fn visit<S: Solution>(&mut self) {
+        let params = day_params::<S>(self.overrides);
+        let run = move |input: &str| S::answers_with(input, &params);
+        let validate = S::validate;
+        self.jobs.push(Job { day: S::DAY, name: S::NAME, run: Box::new(run), validate });
+    }
+}
+
+/// Run the selected days of all profiles on worker threads,
+/// then gather the results into a table per profile in the usual order.
+fn run_parallel(
+    args: &Args,
+    profiles: &[&Profile],
+    baseline: Option<&Snapshot>,
+    groups: &[Vec<&Job>],
+    threads: usize,
+) -> Vec<Record> {
+    let work: Vec<(&Profile, &[&Job])> = profiles
+        .iter()
+        .flat_map(|&profile| groups.iter().map(move |group| (profile, &group[..])))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<_> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(profile, group)) = work.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, solve_first(profile, args.repeat, group)));
+                    }
+                })
+            })
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+    results.sort_unstable_by_key(|&(idx, _)| idx);
+
+    let mut results = results.into_iter().map(|(_, result)| result);
+    let mut records = Vec::new();
+    for &profile in profiles {
+        let mut state = State::new(profile, args, baseline);
+        for _ in groups {
+            state.add_tried(results.next().unwrap());
+        }
+        records.extend(state.print());
+    }
+    records
+}
+
+/// The message of a panic, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic payload")
     }
 }
@@ -0,0 +1,181 @@
+//! Reading the letters off pictures, like the CRT of day 10.
+//!
+//! Puzzles that draw their answer use the same font of 4x6 glyphs,
+//! separated by a blank column.
+
+use std::fmt::Display;
+
+use crate::answer::Bitmap;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Distance between the left edges of consecutive glyphs.
+const ADVANCE: usize = GLYPH_WIDTH + 1;
+
+/// The letters that appear in puzzles, drawn with `#` for pixels that are on.
+#[rustfmt::skip]
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The picture isn't a single row of glyphs.
+    Size { width: usize, height: usize },
+    /// Some glyphs aren't in the font. They are shown as `?` in the text,
+    /// and their positions are counted from 0.
+    Unknown { text: String, positions: Vec<usize> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Size { width, height } => write!(
+                f,
+                "a {width}x{height} picture isn't a row of {GLYPH_WIDTH}x{GLYPH_HEIGHT} glyphs"
+            ),
+            OcrError::Unknown { text, positions } => {
+                let positions: Vec<String> =
+                    positions.iter().map(|pos| (pos + 1).to_string()).collect();
+                write!(f, "unrecognised glyphs {} in `{text}`", positions.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Read the text drawn in a picture, which has to be one row of glyphs,
+/// with or without a blank column after the last one.
+pub fn read(bitmap: &Bitmap) -> Result<String, OcrError> {
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let fits = width.is_multiple_of(ADVANCE) || (width + 1).is_multiple_of(ADVANCE);
+    if height != GLYPH_HEIGHT || !fits {
+        return Err(OcrError::Size { width, height });
+    }
+    let mut text = String::new();
+    let mut positions = Vec::new();
+    for pos in 0..(width + 1) / ADVANCE {
+        match glyph(bitmap, pos * ADVANCE) {
+            Some(c) => text.push(c),
+            None => {
+                text.push('?');
+                positions.push(pos);
+            }
+        }
+    }
+    if positions.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unknown { text, positions })
+    }
+}
+
+/// The letters that [`read`] recognises.
+pub fn letters() -> impl Iterator<Item = char> {
+    FONT.iter().map(|&(c, _)| c)
+}
+
+/// Draw a text in the font, if it only contains letters of the font.
+/// The glyphs are separated by a blank column, without one after the last glyph.
+pub fn draw(text: &str) -> Option<Bitmap> {
+    let glyphs = text
+        .chars()
+        .map(|c| FONT.iter().find(|&&(letter, _)| letter == c).map(|(_, rows)| rows))
+        .collect::<Option<Vec<_>>>()?;
+    let width = (glyphs.len() * ADVANCE).saturating_sub(1);
+    let mut pixels = vec![false; width * GLYPH_HEIGHT];
+    for (pos, rows) in glyphs.iter().enumerate() {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.bytes().enumerate() {
+                pixels[pos * ADVANCE + x + y * width] = pixel == b'#';
+            }
+        }
+    }
+    Some(Bitmap::new(width, pixels))
+}
+
+/// The letter whose glyph starts at column `left`, if the glyph is in the font
+/// and followed by a blank column.
+fn glyph(bitmap: &Bitmap, left: usize) -> Option<char> {
+    // Pixels beyond the right edge are off, so the last glyph needs no blank column.
+    if (0..GLYPH_HEIGHT).any(|y| bitmap.get(left + GLYPH_WIDTH, y)) {
+        return None;
+    }
+    let (c, _) = FONT.iter().find(|(_, rows)| {
+        rows.iter().enumerate().all(|(y, row)| {
+            row.bytes().enumerate().all(|(x, pixel)| bitmap.get(left + x, y) == (pixel == b'#'))
+        })
+    })?;
+    Some(*c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters() {
+        let picture = "\
+###..####.#..#.####.####.#..#..##..####
+#..#....#.#..#.#.......#.#..#.#..#....#
+#..#...#..####.###....#..####.#......#.
+###...#...#..#.#.....#...#..#.#.....#..
+#.#..#....#..#.#....#....#..#.#..#.#...
+#..#.####.#..#.#....####.#..#..##..####";
+        assert_eq!(read(&Bitmap::parse(picture).unwrap()), Ok(String::from("RZHFZHCZ")));
+
+        let all: String = FONT.iter().map(|&(c, _)| c).collect();
+        let rows = (0..GLYPH_HEIGHT).map(|y| {
+            let row: Vec<&str> = FONT.iter().map(|(_, rows)| rows[y]).collect();
+            row.join(".")
+        });
+        let picture = rows.collect::<Vec<_>>().join("\n");
+        assert_eq!(read(&Bitmap::parse(&picture).unwrap()), Ok(all.clone()));
+        assert_eq!(draw(&all).map(|bitmap| read(&bitmap)), Some(Ok(all)));
+        assert_eq!(draw("AB?"), None);
+    }
+
+    #[test]
+    fn errors() {
+        let picture = "\
+.##..#..#.#
+#..#.#..#..
+####.####..
+#..#.#..#..
+#..#.#..#..
+#..#.#..#..";
+        let bitmap = Bitmap::parse(picture).unwrap();
+        assert_eq!(read(&bitmap), Err(OcrError::Size { width: 11, height: 6 }));
+
+        // The first glyph touches the second, and the third isn't a letter.
+        let picture = "\
+.##..#..#.#...
+#..###..#.##..
+####.####.#.#.
+#..#.#..#.#..#
+#..#.#..#.#...
+#..#.#..#.#...";
+        let err = read(&Bitmap::parse(picture).unwrap()).unwrap_err();
+        assert_eq!(err, OcrError::Unknown { text: String::from("?H?"), positions: vec![0, 2] });
+        assert_eq!(err.to_string(), "unrecognised glyphs 1, 3 in `?H?`");
+    }
+}
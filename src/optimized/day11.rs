@@ -101,7 +101,7 @@ fn play<F: Fn(u64) -> u64>(
                 inspections[item.monkey] += 1;
                 item.worry = worry_reduction(monkey.op.apply(item.worry));
 
-                if let Some(repeated) = finder.push((item.monkey, item.worry % monkey.test)) {
+                if let Some(repeated) = finder.push((item.monkey, item.worry)) {
                     break 'outer Some(repeated);
                 }
 
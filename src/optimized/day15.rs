@@ -1,4 +1,6 @@
 use itertools::Itertools;
+
+use crate::params::Day15;
 // The optimization:
 // Grow each diamond by 1, and find its intersections with other diamonds.
 // Check all of these intersections against the original diamonds.
@@ -67,6 +69,7 @@ impl Diamond {
     }
 }
 
+#[allow(dead_code)]
 struct Boundary<'a> {
     diamond: &'a Diamond,
     progress: u32,
@@ -122,12 +125,9 @@ impl Slice {
     }
 }
 
-pub fn run(input: &str) -> (i32, u64) {
-    #[cfg(test)]
-    let line = 10;
-    #[cfg(not(test))]
-    let line = 2_000_000;
-    let search_space = line * 2;
+pub fn run_with(input: &str, params: &Day15) -> (i32, u64) {
+    let line = params.row;
+    let search_space = params.max;
 
     // Parse
     let sensors: Vec<((i32, i32), (i32, i32))> = input
@@ -194,6 +194,8 @@ pub fn run(input: &str) -> (i32, u64) {
 
 #[cfg(test)]
 mod tests {
+    use crate::params::Day15;
+
     #[test]
     fn test() {
         let input = "\
@@ -212,6 +214,6 @@ mod tests {
             Sensor at x=14, y=3: closest beacon is at x=15, y=3\n\
             Sensor at x=20, y=1: closest beacon is at x=15, y=3\n\
         ";
-        assert_eq!(super::run(input), (26, 56000011));
+        assert_eq!(super::run_with(input, &Day15 { row: 10, max: 20 }), (26, 56000011));
     }
 }
@@ -4,6 +4,8 @@ use itertools::Itertools;
 use std::cmp::Reverse;
 use std::mem::swap;
 
+use crate::params::Day16;
+
 // TODO: The choice of abstractions could be better.
 // The branch and bound algorithm could be generalized.
 // One of our instantiations doesn't even track the bound,
@@ -16,30 +18,30 @@ use std::mem::swap;
 //
 // This only makes sense if we can use the same abstractions for day 19.
 
-pub fn run(input: &str) -> (u16, u16) {
+pub fn run_with(input: &str, params: &Day16) -> (u16, u16) {
     let valves = parse(input);
     let graph = preprocess(valves);
 
     // Part 1
     let mut bound = BestBound::new();
-    SearchState::new(&graph, 30, 0).branch_and_bound(&mut bound);
+    SearchState::new(&graph, params.minutes_1, 0).branch_and_bound(&mut bound);
     let res1 = bound.lower_bound();
 
     // Part 2
     let mut bound1 = BestBound::new();
-    SearchState::new(&graph, 26, 0).branch_and_bound(&mut bound1);
+    SearchState::new(&graph, params.minutes_2, 0).branch_and_bound(&mut bound1);
 
     let mut bound2 = BestBound::new();
-    SearchState::new(&graph, 26, bound1.visited).branch_and_bound(&mut bound2);
+    SearchState::new(&graph, params.minutes_2, bound1.visited).branch_and_bound(&mut bound2);
 
     let mut complement_bound = StaticBound::new(bound2.lower_bound, graph.num_valves);
-    SearchState::new(&graph, 26, 0).branch_and_bound(&mut complement_bound);
+    SearchState::new(&graph, params.minutes_2, 0).branch_and_bound(&mut complement_bound);
     let res2 = complement_bound.lower_bound();
 
     (res1, res2)
 }
 
-fn parse(input: &str) -> Vec<Valve> {
+fn parse(input: &str) -> Vec<Valve<'_>> {
     input.lines().map(parse_valve).collect()
 }
 
@@ -49,7 +51,7 @@ struct Valve<'a> {
     tunnels: Vec<&'a str>,
 }
 
-fn parse_valve(line: &str) -> Valve {
+fn parse_valve(line: &str) -> Valve<'_> {
     let words = line.split([' ', '=', ',', ';']).filter(|w| !w.is_empty());
     let mut words = words.skip(1);
     let label = words.next().unwrap();
@@ -339,6 +341,6 @@ mod tests {
             Valve HH has flow rate=22; tunnel leads to valve GG\n\
             Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
             Valve JJ has flow rate=21; tunnel leads to valve II";
-        assert_eq!(super::run(input), (1651, 1707));
+        assert_eq!(super::run_with(input, &Default::default()), (1651, 1707));
     }
 }
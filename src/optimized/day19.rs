@@ -2,7 +2,9 @@ use std::fmt::Display;
 
 use itertools::Itertools;
 
-pub fn run(input: &str) -> (u32, u32) {
+use crate::params::Day19;
+
+pub fn run_with(input: &str, params: &Day19) -> (u32, u32) {
     // Parse
     let blueprints = input.lines().map(Blueprint::parse).collect_vec();
 
@@ -10,7 +12,7 @@ pub fn run(input: &str) -> (u32, u32) {
     let res1 = {
         let mut quality_sum = 0u32;
         for (id, blueprint) in blueprints.iter().enumerate() {
-            let mut state = State::new(blueprint, 24);
+            let mut state = State::new(blueprint, params.minutes_1);
             state.run();
             quality_sum += (id as u32 + 1) * state.best as u32;
         }
@@ -20,9 +22,9 @@ pub fn run(input: &str) -> (u32, u32) {
     // Part 2
     let res2 = {
         let mut best_product = 1u32;
-        let upper = blueprints.len().min(3);
+        let upper = blueprints.len().min(params.blueprints_2);
         for blueprint in &blueprints[0..upper] {
-            let mut state = State::new(blueprint, 32);
+            let mut state = State::new(blueprint, params.minutes_2);
             state.run();
             best_product *= state.best as u32;
         }
@@ -278,12 +280,12 @@ mod tests {
     #[test]
     fn test() {
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.\nBlueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
-        assert_eq!(super::run(input), (33, 56 * 62));
+        assert_eq!(super::run_with(input, &Default::default()), (33, 56 * 62));
     }
 
     #[test]
     fn test_tiny() {
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
-        assert_eq!(super::run(input), (9, 56));
+        assert_eq!(super::run_with(input, &Default::default()), (9, 56));
     }
 }
@@ -55,7 +55,7 @@ impl State {
         let mut blizzard_r = Board { rows: Vec::new() };
         let mut width = 0;
         for line in input.lines() {
-            width = line.as_bytes().len() - 2;
+            width = line.len() - 2;
             if line.starts_with("##") || line.starts_with("#.#") {
                 continue;
             }
@@ -0,0 +1,351 @@
+use std::fmt::{Display, Write};
+use std::path::PathBuf;
+
+use aoc2022::answer::{json_string, Answer};
+use aoc2022::assumptions::Broken;
+use aoc2022::profile::Expected;
+use aoc2022::util::parse::ParseError;
+
+use crate::timing::Stats;
+
+/// Output format of the runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("invalid format `{s}`")),
+        }
+    }
+}
+
+/// The outcome of running one implementation of a day on a profile.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub profile: String,
+    pub day: usize,
+    pub implementation: &'static str,
+    /// Expected answers from `data/<profile>/solutions/`, if known.
+    pub expected: Expected,
+    pub outcome: Result<Solved, Failure>,
+}
+
+/// Answers and timings of an implementation that ran to completion.
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub answers: [Answer; 2],
+    pub stats: Stats,
+    /// Whether other solutions were running at the same time, making the times unreliable.
+    pub parallel: bool,
+}
+
+/// Reason an implementation couldn't produce answers.
+#[derive(Debug, Clone)]
+pub enum Failure {
+    Missing(PathBuf),
+    Parse(ParseError),
+    Panic(String),
+    /// The input breaks assumptions of the implementation, so it wasn't run.
+    Unsupported(Vec<Broken>),
+}
+
+impl Failure {
+    /// Short description for table cells.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Failure::Missing(_) => "missing",
+            Failure::Parse(_) => "parse error",
+            Failure::Panic(_) => "panicked",
+            Failure::Unsupported(_) => "unsupported",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Missing(path) => write!(f, "missing input {}", path.display()),
+            Failure::Parse(err) => write!(f, "parse error at {err}"),
+            Failure::Panic(message) => write!(f, "panicked: {message}"),
+            Failure::Unsupported(broken) => {
+                let assumptions: Vec<&str> = broken.iter().map(|b| b.assumption.as_str()).collect();
+                write!(f, "unsupported input, assumes {}", assumptions.join("; assumes "))
+            }
+        }
+    }
+}
+
+impl Record {
+    /// The answer for a part (0 or 1), if the implementation ran to completion.
+    pub fn answer(&self, part: usize) -> Option<&Answer> {
+        Some(&self.outcome.as_ref().ok()?.answers[part])
+    }
+
+    /// Whether the answer for a part (0 or 1) matches the expected answer, if known.
+    pub fn correct(&self, part: usize) -> Option<bool> {
+        self.expected.check(part, self.answer(part)?)
+    }
+}
+
+/// Render records in a machine-readable format, one entry per part.
+///
+/// The time of an entry is the median time when timing multiple runs.
+pub fn render(format: Format, records: &[Record], parts: [bool; 2]) -> String {
+    let entries = records
+        .iter()
+        .flat_map(|record| (0..2).map(move |part| (record, part)))
+        .filter(|&(_, part)| parts[part]);
+    let correct = |record: &Record, part| match record.correct(part) {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "",
+    };
+    // Timing fields in the order time_ns, min_ns, mean_ns, std_dev_ns, runs, time_reliable.
+    let times = |record: &Record| match &record.outcome {
+        Ok(Solved { stats, parallel, .. }) => [
+            stats.median.as_nanos().to_string(),
+            stats.min.as_nanos().to_string(),
+            stats.mean.as_nanos().to_string(),
+            stats.std_dev.as_nanos().to_string(),
+            stats.runs.to_string(),
+            (!parallel).to_string(),
+        ],
+        Err(_) => Default::default(),
+    };
+    let error = |record: &Record| record.outcome.as_ref().err().map(|e| e.to_string());
+    let or_null = |s: String| if s.is_empty() { String::from("null") } else { s };
+
+    let mut out = String::new();
+    match format {
+        Format::Table => unreachable!("tables are rendered by the runner"),
+        Format::Json => {
+            out.push('[');
+            for (idx, (record, part)) in entries.enumerate() {
+                let sep = if idx == 0 { "" } else { "," };
+                let [time, min, mean, std_dev, runs, reliable] = times(record).map(or_null);
+                write!(
+                    out,
+                    "{sep}\n  {{\"profile\": {}, \"day\": {}, \"implementation\": {}, \
+                     \"part\": {}, \"answer\": {}, \"time_ns\": {time}, \"min_ns\": {min}, \
+                     \"mean_ns\": {mean}, \"std_dev_ns\": {std_dev}, \"runs\": {runs}, \
+                     \"time_reliable\": {reliable}, \"correct\": {}, \"error\": {}}}",
+                    json_string(&record.profile),
+                    record.day,
+                    json_string(record.implementation),
+                    part + 1,
+                    record.answer(part).map_or(String::from("null"), Answer::to_json),
+                    or_null(correct(record, part).to_owned()),
+                    error(record).map_or(String::from("null"), |e| json_string(&e)),
+                )
+                .unwrap();
+            }
+            out.push_str("\n]\n");
+        }
+        Format::Csv => {
+            out.push_str(
+                "profile,day,implementation,part,answer,time_ns,min_ns,mean_ns,std_dev_ns,runs,\
+                 time_reliable,correct,error\n",
+            );
+            for (record, part) in entries {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{}",
+                    csv_field(&record.profile),
+                    record.day,
+                    csv_field(record.implementation),
+                    part + 1,
+                    csv_field(&record.answer(part).map(Answer::to_string).unwrap_or_default()),
+                    times(record).join(","),
+                    correct(record, part),
+                    csv_field(&error(record).unwrap_or_default()),
+                )
+                .unwrap();
+            }
+        }
+        Format::Markdown => {
+            out.push_str(
+                "| Profile | Day | Implementation | Part | Answer | Time (ns) | Correct |\n",
+            );
+            out.push_str("|---|--:|---|--:|--:|--:|---|\n");
+            for (record, part) in entries {
+                writeln!(
+                    out,
+                    "| {} | {} | `{}` | {} | {} | {} | {} |",
+                    markdown_cell(&record.profile),
+                    record.day,
+                    record.implementation,
+                    part + 1,
+                    match &record.outcome {
+                        Ok(solved) => markdown_answer(&solved.answers[part].to_string()),
+                        Err(failure) => format!("*{}*", failure.label()),
+                    },
+                    match &record.outcome {
+                        Ok(Solved { stats, parallel: true, .. }) => {
+                            format!("{} (parallel)", stats.median.as_nanos())
+                        }
+                        _ => times(record)[0].clone(),
+                    },
+                    correct(record, part),
+                )
+                .unwrap();
+            }
+        }
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// Parse CSV as written by [`render`], with fields quoted where needed.
+pub fn parse_csv(s: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// Multi-line answers are kept intact as a `<pre>` block with explicit line breaks.
+fn markdown_answer(s: &str) -> String {
+    let s = markdown_cell(s.trim_end());
+    if s.contains('\n') {
+        format!("<pre>{}</pre>", s.replace('\n', "<br>"))
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2022::answer::Bitmap;
+    use std::time::Duration;
+
+    fn record(answers: [Answer; 2], expected: [Option<&str>; 2]) -> Record {
+        Record {
+            profile: String::from("github"),
+            day: 10,
+            implementation: "solutions::day10",
+            expected: Expected(expected.map(|e| e.map(String::from))),
+            outcome: Ok(Solved {
+                answers,
+                stats: Stats::from_samples(&mut [Duration::from_nanos(1234)]),
+                parallel: false,
+            }),
+        }
+    }
+
+    fn failed(failure: Failure) -> Record {
+        Record { outcome: Err(failure), ..record([Answer::None, Answer::None], [Some("1"), None]) }
+    }
+
+    #[test]
+    fn json() {
+        let bitmap = Bitmap::new(2, vec![true, false, false, true]);
+        let records = [record([13140.into(), bitmap.into()], [Some("13140"), None])];
+        let out = render(Format::Json, &records, [true, true]);
+        assert!(out.contains(r#""answer": 13140, "time_ns": 1234, "min_ns": 1234,"#));
+        assert!(out.contains(r#""answer": ["█░", "░█"]"#));
+        assert!(out.contains(r#""time_reliable": true, "correct": true, "error": null}"#));
+        assert!(out.contains(r#""time_reliable": true, "correct": null, "error": null}"#));
+    }
+
+    #[test]
+    fn csv() {
+        let answers = [1.into(), "a,\"b\"\nc".into()];
+        let records = [record(answers, [Some("2"), Some("a,\"b\"\nc")])];
+        let out = render(Format::Csv, &records, [true, true]);
+        assert!(out.starts_with("profile,day,implementation,part,answer,time_ns,"));
+        assert!(out.contains("\ngithub,10,solutions::day10,1,1,1234,1234,1234,0,1,true,false,\n"));
+        assert!(out.contains(
+            "\ngithub,10,solutions::day10,2,\"a,\"\"b\"\"\nc\",1234,1234,1234,0,1,true,true,\n"
+        ));
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let records = [record([1.into(), "a,\"b\"\nc".into()], [None, None])];
+        let rows = parse_csv(&render(Format::Csv, &records, [true, true]));
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+        assert_eq!(rows[2][4], "a,\"b\"\nc");
+    }
+
+    #[test]
+    fn markdown_part_selection() {
+        let bitmap = Bitmap::new(2, vec![true, false, false, true]);
+        let records = [record([1.into(), bitmap.into()], [None, None])];
+        let out = render(Format::Markdown, &records, [false, true]);
+        assert_eq!(out.lines().count(), 3);
+        assert!(out.contains("<pre>█░<br>░█</pre>"));
+    }
+
+    #[test]
+    fn failures() {
+        let error = ParseError { line: 2, column: 4, message: String::from("expected `,`") };
+        let missing = Failure::Missing("day10.txt".into());
+        let records = [failed(Failure::Parse(error)), failed(missing)];
+        let json = render(Format::Json, &records, [true, false]);
+        assert!(json.contains(r#""answer": null, "time_ns": null, "min_ns": null,"#));
+        assert!(json.contains(r#""runs": null, "time_reliable": null, "correct": null,"#));
+        assert!(json.contains(r#""error": "parse error at line 2, column 4: expected `,`"}"#));
+        let csv = render(Format::Csv, &records, [true, false]);
+        assert!(csv.contains("\ngithub,10,solutions::day10,1,,,,,,,,,missing input day10.txt\n"));
+        let markdown = render(Format::Markdown, &records, [true, false]);
+        assert!(markdown.contains("| 1 | *parse error* |  |  |"));
+    }
+
+    #[test]
+    fn parallel() {
+        let mut parallel = record([1.into(), 2.into()], [None, None]);
+        if let Ok(solved) = &mut parallel.outcome {
+            solved.parallel = true;
+        }
+        let json = render(Format::Json, &[parallel.clone()], [true, false]);
+        assert!(json.contains(r#""time_reliable": false"#));
+        let markdown = render(Format::Markdown, &[parallel], [true, false]);
+        assert!(markdown.contains("| 1 | 1234 (parallel) |"));
+    }
+}
@@ -0,0 +1,168 @@
+//! Parameters of the puzzles that are given in the text rather than in the input.
+//!
+//! The examples often use other values than the real puzzles,
+//! like the row to scan on day 15 or the size of the cube on day 22.
+//! The defaults are the values of the real puzzles.
+//! The runner can override them with `--param day15.row=10`,
+//! and tests construct them directly.
+//! Overrides outside of the range given after a field, like `cube_size [1..]`, are rejected.
+
+use std::fmt::Debug;
+
+/// Parameters of a day, which can be set by name.
+pub trait Params: Debug + Default + Clone + Send + Sync + 'static {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+}
+
+/// Days without parameters.
+impl Params for () {
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("no parameter `{name}`"))
+    }
+}
+
+macro_rules! params {
+    ( $(
+        $(#[$meta:meta])*
+        $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident $([$range:expr])?: $ty:ty = $default:expr, )+
+        }
+    )+ ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name {
+                $( $(#[$field_meta])* pub $field: $ty, )+
+            }
+
+            impl Default for $name {
+                fn default() -> Self {
+                    Self { $( $field: $default, )+ }
+                }
+            }
+
+            impl Params for $name {
+                fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                    match name {
+                        $( stringify!($field) => {
+                            let parsed: $ty = value
+                                .parse()
+                                .map_err(|_| format!("invalid value `{value}` for `{name}`"))?;
+                            $( if !($range).contains(&parsed) {
+                                return Err(format!(
+                                    "invalid value `{value}` for `{name}`, expected {:?}",
+                                    $range
+                                ));
+                            } )?
+                            self.$field = parsed;
+                        } )+
+                        _ => return Err(format!("no parameter `{name}`")),
+                    }
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+params! {
+    /// Day 15: Beacon Exclusion Zone.
+    Day15 {
+        /// Row to count the positions that can't contain a beacon in part 1.
+        row: i32 = 2_000_000,
+        /// Largest coordinate of the distress beacon in part 2.
+        max [0..]: i32 = 4_000_000,
+    }
+
+    /// Day 16: Proboscidea Volcanium.
+    Day16 {
+        /// Minutes to release pressure alone.
+        minutes_1: u16 = 30,
+        /// Minutes to release pressure together with the elephant.
+        minutes_2: u16 = 26,
+    }
+
+    /// Day 17: Pyroclastic Flow.
+    Day17 {
+        /// Rocks to drop before measuring the tower in part 1.
+        rocks_1: usize = 2022,
+        /// Rocks to drop before measuring the tower in part 2.
+        rocks_2: usize = 1_000_000_000_000,
+    }
+
+    /// Day 19: Not Enough Minerals.
+    Day19 {
+        /// Minutes to collect geodes for each blueprint in part 1.
+        minutes_1: u8 = 24,
+        /// Minutes to collect geodes for each remaining blueprint in part 2.
+        minutes_2: u8 = 32,
+        /// Number of blueprints that the elephants didn't eat in part 2.
+        blueprints_2: usize = 3,
+    }
+
+    /// Day 22: Monkey Map.
+    Day22 {
+        /// Side length of the faces of the cube.
+        cube_size [1..]: usize = 50,
+    }
+}
+
+/// Override of a parameter of a day, written as `day15.row=10`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub day: usize,
+    pub name: String,
+    pub value: String,
+}
+
+impl Override {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid parameter `{s}`, expected e.g. `day15.row=10`");
+        let (day, rest) =
+            s.strip_prefix("day").and_then(|s| s.split_once('.')).ok_or_else(invalid)?;
+        let (name, value) = rest.split_once('=').ok_or_else(invalid)?;
+        match day.parse() {
+            Ok(day) if (1..=25).contains(&day) => {
+                Ok(Self { day, name: name.to_owned(), value: value.to_owned() })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Parameters of a day, with the overrides for that day applied.
+pub fn resolve<P: Params>(day: usize, overrides: &[Override]) -> Result<P, String> {
+    let mut params = P::default();
+    for o in overrides.iter().filter(|o| o.day == day) {
+        params.set(&o.name, &o.value).map_err(|err| format!("day {day}: {err}"))?;
+    }
+    Ok(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides() {
+        let overrides = ["day15.row=10", "day15.max=20", "day22.cube_size=4"]
+            .map(|s| Override::parse(s).unwrap());
+        assert_eq!(resolve(15, &overrides), Ok(Day15 { row: 10, max: 20 }));
+        assert_eq!(resolve(22, &overrides), Ok(Day22 { cube_size: 4 }));
+        assert_eq!(resolve(16, &overrides), Ok(Day16::default()));
+        assert_eq!(resolve::<()>(15, &overrides), Err(String::from("day 15: no parameter `row`")));
+
+        let invalid = [Override::parse("day15.row=ten").unwrap()];
+        assert!(resolve::<Day15>(15, &invalid).is_err());
+        let out_of_range = [Override::parse("day22.cube_size=0").unwrap()];
+        assert_eq!(
+            resolve::<Day22>(22, &out_of_range),
+            Err(String::from("day 22: invalid value `0` for `cube_size`, expected 1.."))
+        );
+        let out_of_range = [Override::parse("day15.max=-1").unwrap()];
+        assert!(resolve::<Day15>(15, &out_of_range).is_err());
+        assert!(Override::parse("day26.row=10").is_err());
+        assert!(Override::parse("day15.row").is_err());
+        assert!(Override::parse("15.row=10").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Access to the inputs and solutions stored in `data/<PROFILE_NAME>/`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answer::Answer;
+
+/// A set of inputs and (optionally) solutions stored in `data/<name>/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Profile {
+    /// Prefix of the profiles written by `fuzz`, with inputs that some implementation fails on.
+    /// They are left out of [`Profile::all`], and only used when selected by name.
+    pub const FUZZ_PREFIX: &'static str = "fuzz-";
+
+    /// All profiles in the `data` directory, sorted by name, except the ones from fuzzing.
+    pub fn all() -> io::Result<Vec<Self>> {
+        Self::all_in("data")
+    }
+
+    pub fn all_in<P: AsRef<Path>>(data: P) -> io::Result<Vec<Self>> {
+        let mut profiles = Vec::new();
+        for entry in std::fs::read_dir(data)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            if !path.is_dir() || name.starts_with(Self::FUZZ_PREFIX) {
+                continue;
+            }
+            profiles.push(Profile { name, path });
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+
+    /// A profile in the `data` directory by name, including the ones from fuzzing.
+    pub fn named(name: &str) -> Option<Self> {
+        let path = Path::new("data").join(name);
+        path.is_dir().then(|| Profile { name: name.to_owned(), path })
+    }
+
+    pub fn input_path(&self, day: usize) -> PathBuf {
+        self.path.join("inputs").join(format!("day{day:02}.txt"))
+    }
+
+    pub fn solution_path(&self, day: usize) -> PathBuf {
+        self.path.join("solutions").join(format!("day{day:02}.txt"))
+    }
+
+    /// The input of a day, with Windows line endings turned into `\n`
+    /// as the solutions expect.
+    pub fn input(&self, day: usize) -> io::Result<String> {
+        let input = std::fs::read_to_string(self.input_path(day))?;
+        Ok(input.replace("\r\n", "\n"))
+    }
+
+    /// The expected answers for a day, if there is a solution file for it.
+    pub fn expected(&self, day: usize) -> Option<Expected> {
+        let solution = std::fs::read_to_string(self.solution_path(day)).ok()?;
+        Some(Expected::parse(&solution))
+    }
+
+    /// Write the solution file for a day.
+    pub fn save_expected(&self, day: usize, expected: &Expected) -> io::Result<()> {
+        let path = self.solution_path(day);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, expected.to_string())
+    }
+}
+
+/// Expected answers of a day, as read from a solution file.
+///
+/// A solution file contains the answer to part 1,
+/// optionally followed by an empty line and the answer to part 2.
+/// The answer to part 2 may span multiple lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected(pub [Option<String>; 2]);
+
+impl Expected {
+    pub fn parse(solution: &str) -> Self {
+        let solution = solution.replace("\r\n", "\n");
+        let answer = |s: &str| {
+            let s = s.trim_end();
+            (!s.is_empty()).then(|| s.to_owned())
+        };
+        match solution.split_once("\n\n") {
+            Some((part1, part2)) => Expected([answer(part1), answer(part2)]),
+            None => Expected([answer(&solution), None]),
+        }
+    }
+
+    /// Compare an answer to the expected answer of a part (0 or 1),
+    /// returning `None` if the expected answer isn't known.
+    pub fn check(&self, part: usize, actual: &Answer) -> Option<bool> {
+        let expected = self.0[part].as_ref()?;
+        Some(actual == expected.as_str())
+    }
+}
+
+/// Formats the answers as a solution file.
+/// An unknown answer to part 1 is left empty if the answer to part 2 is known.
+impl std::fmt::Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            [None, None] => Ok(()),
+            [part1, None] => writeln!(f, "{}", part1.as_deref().unwrap_or_default()),
+            [part1, Some(part2)] => {
+                writeln!(f, "{}\n\n{part2}", part1.as_deref().unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all() {
+        let data = std::env::temp_dir().join(format!("aoc2022-data-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data);
+        for name in ["github", "fuzz-11-3", "seed-7"] {
+            std::fs::create_dir_all(data.join(name)).unwrap();
+        }
+        std::fs::write(data.join("notes.txt"), "").unwrap();
+        let profiles = Profile::all_in(&data).unwrap();
+        let names: Vec<&str> = profiles.iter().map(|profile| profile.name.as_str()).collect();
+        assert_eq!(names, ["github", "seed-7"]);
+        std::fs::remove_dir_all(&data).unwrap();
+    }
+
+    #[test]
+    fn parse() {
+        let both = Expected::parse("23987234\n\n43742\n");
+        assert_eq!(both.0, [Some("23987234".into()), Some("43742".into())]);
+        let first = Expected::parse("2=-1=0\n");
+        assert_eq!(first.0, [Some("2=-1=0".into()), None]);
+        let grid = Expected::parse("13140\r\n\r\n##..\n#..#\n");
+        assert_eq!(grid.0, [Some("13140".into()), Some("##..\n#..#".into())]);
+    }
+
+    #[test]
+    fn check() {
+        let expected = Expected::parse("15\n\n12\n");
+        assert_eq!(expected.check(0, &Answer::from(15u32)), Some(true));
+        assert_eq!(expected.check(1, &Answer::from(13u32)), Some(false));
+        assert_eq!(expected.check(1, &Answer::from("12")), Some(true));
+        assert_eq!(Expected::parse("15").check(1, &Answer::from(12u32)), None);
+    }
+
+    #[test]
+    fn display() {
+        for solution in ["23987234\n\n43742\n", "2=-1=0\n", "13140\n\n##..\n#..#\n", ""] {
+            assert_eq!(Expected::parse(solution).to_string(), solution);
+        }
+    }
+}
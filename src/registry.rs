@@ -0,0 +1,181 @@
+//! Registry of all implementations for each day.
+//!
+//! The first implementation listed for a day is the one used by the runner,
+//! the integration tests and the benchmarks.
+//! Switching a day to another implementation is done by reordering its entry.
+//! Days with parameters in the puzzle text name the type of their parameters, like `15 [Day15]`.
+
+use crate::answer::Answer;
+use crate::assumptions::{self, Broken};
+use crate::formats;
+use crate::params::{Day15, Day16, Day17, Day19, Day22, Params};
+use crate::util::parse::ParseError;
+
+/// A solution to both parts of a day.
+pub trait Solution {
+    const DAY: usize;
+    /// Module path of the implementation, e.g. `optimized::day13`.
+    const NAME: &'static str;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+    /// Parameters given in the puzzle text, `()` for most days.
+    type Params: Params;
+    fn run_with(input: &str, params: &Self::Params) -> (Self::Part1, Self::Part2);
+
+    /// Run with the parameters of the real puzzle.
+    fn run(input: &str) -> (Self::Part1, Self::Part2) {
+        Self::run_with(input, &Self::Params::default())
+    }
+
+    /// Run with the given parameters, converting the answers to [`Answer`]s.
+    fn answers_with(input: &str, params: &Self::Params) -> [Answer; 2] {
+        let (res1, res2) = Self::run_with(input, params);
+        [res1.into(), res2.into()]
+    }
+
+    /// Check the format of the input before running,
+    /// reporting where it is malformed instead of panicking.
+    fn try_run(input: &str) -> Result<(Self::Part1, Self::Part2), ParseError> {
+        formats::check(Self::DAY, input)?;
+        Ok(Self::run(input))
+    }
+
+    /// Assumptions of this implementation that an input with the right format breaks,
+    /// see [`assumptions`].
+    fn validate(input: &str) -> Vec<Broken> {
+        let mut broken = assumptions::validate(Self::DAY, input);
+        broken.retain(|broken| broken.implementation == Self::NAME);
+        broken
+    }
+}
+
+/// Callback for iterating over the statically typed solutions of the registry.
+pub trait Visitor {
+    fn visit<S: Solution>(&mut self);
+}
+
+/// Implementations from the `solutions` module.
+pub struct Solutions<const DAY: usize>;
+
+/// Implementations from the `optimized` module.
+pub struct Optimized<const DAY: usize>;
+
+macro_rules! implementation {
+    (solutions, $n:literal) => { Solutions<$n> };
+    (optimized, $n:literal) => { Optimized<$n> };
+}
+
+macro_rules! registry {
+    (
+        $( $n:literal $([$params:ty])?: $( $module:ident::$day:ident ($a1:ty, $a2:ty) ),+ ; )+
+    ) => {
+        $( registry!(@day $n [$($params)?] $( $module::$day ($a1, $a2) ),+); )+
+
+        /// Visit the chosen implementation of every day in order.
+        pub fn visit<V: Visitor>(visitor: &mut V) {
+            $( registry!(@first visitor, $n, $($module),+); )+
+        }
+
+        /// Visit every implementation of every day in order.
+        pub fn visit_all<V: Visitor>(visitor: &mut V) {
+            $($( visitor.visit::<implementation!($module, $n)>(); )+)+
+        }
+    };
+    // Days with parameters have a `run_with` taking them, the others just a `run`.
+    (@day $n:literal [] $( $module:ident::$day:ident ($a1:ty, $a2:ty) ),+) => {
+        $(
+            impl Solution for implementation!($module, $n) {
+                const DAY: usize = $n;
+                const NAME: &'static str = concat!(stringify!($module), "::", stringify!($day));
+                type Part1 = $a1;
+                type Part2 = $a2;
+                type Params = ();
+                fn run_with(input: &str, _params: &()) -> ($a1, $a2) {
+                    crate::$module::$day::run(input)
+                }
+            }
+        )+
+    };
+    (@day $n:literal [$params:ty] $( $module:ident::$day:ident ($a1:ty, $a2:ty) ),+) => {
+        $(
+            impl Solution for implementation!($module, $n) {
+                const DAY: usize = $n;
+                const NAME: &'static str = concat!(stringify!($module), "::", stringify!($day));
+                type Part1 = $a1;
+                type Part2 = $a2;
+                type Params = $params;
+                fn run_with(input: &str, params: &$params) -> ($a1, $a2) {
+                    crate::$module::$day::run_with(input, params)
+                }
+            }
+        )+
+    };
+    (@first $visitor:ident, $n:literal, $module:ident $(, $rest:ident)*) => {
+        $visitor.visit::<implementation!($module, $n)>()
+    };
+}
+
+#[rustfmt::skip]
+registry! {
+    1: optimized::day01 (u32, u32), solutions::day01 (u32, u32);
+    2: solutions::day02 (u32, u32);
+    3: solutions::day03 (u32, u32);
+    4: solutions::day04 (u32, u32);
+    5: solutions::day05 (String, String);
+    6: optimized::day06 (usize, usize), solutions::day06 (usize, usize);
+    7: solutions::day07 (u32, u32);
+    8: solutions::day08 (usize, u32);
+    9: solutions::day09 (usize, usize);
+    10: solutions::day10 (i32, crate::solutions::day10::Res2);
+    11: optimized::day11 (u64, u64), solutions::day11 (usize, usize);
+    12: solutions::day12 (usize, usize);
+    13: optimized::day13 (usize, u32), solutions::day13 (usize, usize);
+    14: optimized::day14 (usize, usize), solutions::day14 (usize, usize);
+    15 [Day15]: optimized::day15 (i32, u64), solutions::day15 (i32, u64);
+    16 [Day16]: optimized::day16 (u16, u16), solutions::day16 (u64, u64);
+    17 [Day17]: solutions::day17 (usize, usize);
+    18: solutions::day18 (usize, usize);
+    19 [Day19]: optimized::day19 (u32, u32), solutions::day19 (u64, u64);
+    20: solutions::day20 (i64, i64);
+    21: solutions::day21 (i64, i64);
+    22 [Day22]: solutions::day22 (usize, usize);
+    23: solutions::day23 (u64, u64);
+    24: optimized::day24 (usize, usize), solutions::day24 (usize, usize);
+    25: solutions::day25 (String, ());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Collect(Vec<(usize, &'static str)>);
+
+    impl Visitor for Collect {
+        fn visit<S: Solution>(&mut self) {
+            self.0.push((S::DAY, S::NAME));
+        }
+    }
+
+    #[test]
+    fn one_chosen_per_day() {
+        let mut chosen = Collect(Vec::new());
+        visit(&mut chosen);
+        let days: Vec<usize> = chosen.0.iter().map(|&(day, _)| day).collect();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert_eq!(chosen.0[12].1, "optimized::day13");
+        assert_eq!(chosen.0[1].1, "solutions::day02");
+
+        let mut all = Collect(Vec::new());
+        visit_all(&mut all);
+        // Every implementation in `src/solutions/` and `src/optimized/` is registered,
+        // except for the ones that don't give the right answers yet.
+        let unfinished = ["optimized::day23"];
+        let files: usize = ["src/solutions", "src/optimized"]
+            .map(|dir| std::fs::read_dir(dir).unwrap().count())
+            .iter()
+            .sum();
+        assert_eq!(all.0.len(), files - unfinished.len());
+        assert!(all.0.iter().all(|(_, name)| !unfinished.contains(name)));
+        assert!(chosen.0.iter().all(|x| all.0.contains(x)));
+    }
+}
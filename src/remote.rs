@@ -0,0 +1,346 @@
+//! Downloading inputs and submitting answers.
+//!
+//! Every profile has its own session token, read from `data/<profile>/session`
+//! or the `AOC_SESSION` environment variable.
+//! Requests are spaced out by at least the rate limit, also across runs,
+//! and verdicts of submitted answers are cached in `data/<profile>/cache/`
+//! so that the same answer is never submitted twice.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use aoc2022::answer::Answer;
+use aoc2022::profile::Profile;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u32 = 2022;
+const USER_AGENT: &str = "github.com/korreman/aoc2022";
+
+/// Client for the puzzle server, acting on behalf of a single profile.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    rate_limit: Duration,
+    cache: PathBuf,
+}
+
+impl Client {
+    pub fn new(profile: &Profile, base_url: &str, rate_limit: Duration) -> Result<Self, String> {
+        let session = match std::fs::read_to_string(profile.path.join("session")) {
+            Ok(session) => session.trim().to_owned(),
+            Err(_) => std::env::var("AOC_SESSION").map_err(|_| {
+                format!(
+                    "no session token for `{}`, put it in {} or set AOC_SESSION",
+                    profile.name,
+                    profile.path.join("session").display()
+                )
+            })?,
+        };
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            rate_limit,
+            cache: profile.path.join("cache"),
+        })
+    }
+
+    /// Wait until the rate limit allows another request.
+    fn throttle(&self) -> Result<(), String> {
+        let last_request = self.cache.join("last-request");
+        if let Ok(modified) = std::fs::metadata(&last_request).and_then(|m| m.modified()) {
+            let elapsed = SystemTime::now().duration_since(modified).unwrap_or_default();
+            if let Some(remaining) = self.rate_limit.checked_sub(elapsed) {
+                std::thread::sleep(remaining);
+            }
+        }
+        std::fs::create_dir_all(&self.cache)
+            .and_then(|_| std::fs::write(&last_request, ""))
+            .map_err(|err| format!("couldn't write {}: {err}", last_request.display()))
+    }
+
+    /// Send a request with the session cookie, and a form body if given.
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, String> {
+        self.throttle()?;
+        let url = request.url().to_owned();
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response.into_string().map_err(|err| format!("{url}: {err}")),
+            Err(ureq::Error::Status(status, _)) => Err(format!("{url} returned status {status}")),
+            Err(err) => Err(format!("{url}: {err}")),
+        }
+    }
+
+    pub fn input(&self, day: usize) -> Result<String, String> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        self.send(self.agent.get(&url), None)
+    }
+
+    pub fn submit(&self, day: usize, part: usize, answer: &str) -> Result<Verdict, String> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let level = (part + 1).to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        let page = self.send(self.agent.post(&url), Some(&form))?;
+        Ok(Verdict::parse(&page))
+    }
+
+    fn submissions_path(&self) -> PathBuf {
+        self.cache.join("submissions.tsv")
+    }
+
+    /// The verdict of an answer that has been submitted before.
+    fn cached(&self, day: usize, part: usize, answer: &str) -> Option<Verdict> {
+        let submissions = std::fs::read_to_string(self.submissions_path()).ok()?;
+        submissions.lines().find_map(|line| {
+            let [d, p, verdict, a] = line.splitn(4, '\t').collect::<Vec<_>>()[..] else {
+                return None;
+            };
+            let matches = d == day.to_string() && p == (part + 1).to_string() && a == answer;
+            matches.then(|| Verdict::parse_cached(verdict)).flatten()
+        })
+    }
+
+    fn cache_verdict(&self, day: usize, part: usize, answer: &str, verdict: &Verdict) {
+        use std::io::Write;
+        let line = format!("{day}\t{}\t{verdict}\t{answer}\n", part + 1);
+        let path = self.submissions_path();
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&path);
+        if let Err(err) = file.and_then(|mut file| file.write_all(line.as_bytes())) {
+            eprintln!("warning: couldn't cache verdict in {}: {err}", path.display());
+        }
+    }
+}
+
+/// Response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong answer, possibly with a hint such as `too high`.
+    Incorrect(Option<String>),
+    /// An answer was submitted too recently, possibly with the time left to wait.
+    TooSoon(Option<String>),
+    /// The part has already been solved, or isn't unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn parse(page: &str) -> Self {
+        let between = |start: &str, end: &str| {
+            let rest = &page[page.find(start)? + start.len()..];
+            Some(rest[..rest.find(end)?].to_owned())
+        };
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            let hint = ["too high", "too low"].into_iter().find(|hint| page.contains(hint));
+            Verdict::Incorrect(hint.map(String::from))
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::TooSoon(between("You have ", " left to wait"))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Parse a verdict as written to the cache. Only definite verdicts are cached.
+    fn parse_cached(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "incorrect" => Some(Verdict::Incorrect(None)),
+            _ => Some(Verdict::Incorrect(Some(s.strip_prefix("incorrect, ")?.to_owned()))),
+        }
+    }
+
+    fn is_definite(&self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::Incorrect(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect(None) => write!(f, "incorrect"),
+            Verdict::Incorrect(Some(hint)) => write!(f, "incorrect, {hint}"),
+            Verdict::TooSoon(None) => write!(f, "too soon"),
+            Verdict::TooSoon(Some(wait)) => write!(f, "too soon, {wait} left to wait"),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Verdict::Unknown => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+/// Download the inputs of the given days that are missing from a profile.
+pub fn fetch(
+    client: &Client,
+    profile: &Profile,
+    days: impl IntoIterator<Item = usize>,
+) -> Result<(), String> {
+    for day in days {
+        let path = profile.input_path(day);
+        if path.exists() {
+            continue;
+        }
+        let input = client.input(day)?;
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, input))
+            .map_err(|err| format!("couldn't write {}: {err}", path.display()))?;
+        println!("Downloaded {}", path.display());
+    }
+    Ok(())
+}
+
+/// Submit an answer for a part (0 or 1) of a day,
+/// recording it in the solution file of the profile if it is correct.
+///
+/// Answers are compared to the solution file and to earlier submissions first,
+/// in which case nothing is sent.
+pub fn submit(
+    client: &Client,
+    profile: &Profile,
+    day: usize,
+    part: usize,
+    answer: &str,
+) -> Result<Verdict, String> {
+    let answer = answer.trim();
+    let mut expected = profile.expected(day).unwrap_or_default();
+    if let Some(correct) = expected.check(part, &Answer::from(answer)) {
+        return Ok(if correct { Verdict::Correct } else { Verdict::Incorrect(None) });
+    }
+    if let Some(verdict) = client.cached(day, part, answer) {
+        return Ok(verdict);
+    }
+
+    let verdict = client.submit(day, part, answer)?;
+    if verdict.is_definite() {
+        client.cache_verdict(day, part, answer, &verdict);
+    }
+    if verdict == Verdict::Correct {
+        expected.0[part] = Some(answer.to_owned());
+        profile.save_expected(day, &expected).map_err(|err| {
+            format!("couldn't write {}: {err}", profile.solution_path(day).display())
+        })?;
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// A profile in a fresh temporary directory, with a session token.
+    fn profile(name: &str) -> Profile {
+        let path = std::env::temp_dir().join(format!("aoc2022-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("session"), "token\n").unwrap();
+        Profile { name: name.to_owned(), path }
+    }
+
+    /// Serve one connection per response, returning the requests received,
+    /// each as the request line, the cookie and the body.
+    fn mock(responses: Vec<&'static str>) -> (String, JoinHandle<Vec<[String; 3]>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let (mut cookie, mut length) = (String::new(), 0);
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let Some((name, value)) = header.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = value.to_owned(),
+                        "content-length" => length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+                requests.push([request_line.trim_end().to_owned(), cookie, body]);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn fetch_missing() {
+        let profile = profile("fetch");
+        let existing = profile.input_path(2);
+        std::fs::create_dir_all(existing.parent().unwrap()).unwrap();
+        std::fs::write(&existing, "A Y\n").unwrap();
+
+        let (url, server) = mock(vec!["1000\n2000\n"]);
+        let client = Client::new(&profile, &url, Duration::ZERO).unwrap();
+        fetch(&client, &profile, [1, 2]).unwrap();
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0][0], "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(requests[0][1], "session=token");
+        assert_eq!(profile.input(1).unwrap(), "1000\n2000\n");
+        assert_eq!(profile.input(2).unwrap(), "A Y\n");
+    }
+
+    #[test]
+    fn submit_and_cache() {
+        let profile = profile("submit");
+        let (url, server) = mock(vec![
+            "<p>That's not the right answer; your answer is too high.</p>",
+            "<p>That's the right answer! You are one gold star closer.</p>",
+        ]);
+        let client = Client::new(&profile, &url, Duration::ZERO).unwrap();
+        let too_high = Verdict::Incorrect(Some(String::from("too high")));
+        assert_eq!(submit(&client, &profile, 5, 1, "9000"), Ok(too_high.clone()));
+        // Cached, so nothing is sent
+        assert_eq!(submit(&client, &profile, 5, 1, "9000"), Ok(too_high));
+        assert_eq!(submit(&client, &profile, 5, 1, "4711\n"), Ok(Verdict::Correct));
+        assert_eq!(submit(&client, &profile, 5, 1, "4711"), Ok(Verdict::Correct));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0][0], "POST /2022/day/5/answer HTTP/1.1");
+        assert_eq!(requests[1][2], "level=2&answer=4711");
+        let expected = profile.expected(5).unwrap();
+        assert_eq!(expected.0, [None, Some(String::from("4711"))]);
+    }
+
+    #[test]
+    fn verdicts() {
+        let too_soon = "You gave an answer too recently. You have 43s left to wait.";
+        assert_eq!(Verdict::parse(too_soon), Verdict::TooSoon(Some(String::from("43s"))));
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+        for verdict in [Verdict::Correct, Verdict::Incorrect(Some(String::from("too low")))] {
+            assert_eq!(Verdict::parse_cached(&verdict.to_string()), Some(verdict));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write;
+use std::path::Path;
+
+/// Answer types of a new day, until the real ones are known.
+const ANSWERS: &str = "(usize, usize)";
+
+const STUB: &str = r#"pub fn run(input: &str) -> (usize, usize) {
+    let _ = input;
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test() {
+        // The example from the puzzle description.
+        let input = "\
+            ";
+        assert_eq!(super::run(input), (0, 0));
+    }
+}
+"#;
+
+/// Stub of a day with parameters, taking them like the `registry!` expects.
+const PARAMS_STUB: &str = r#"use crate::params::PARAMS;
+
+pub fn run_with(input: &str, params: &PARAMS) -> (usize, usize) {
+    let _ = (input, params);
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        // The example from the puzzle description, with the parameters it uses.
+        let input = "\
+            ";
+        assert_eq!(run_with(input, &PARAMS::default()), (0, 0));
+    }
+}
+"#;
+
+/// The stub of a day, taking the type of its parameters if it has some.
+fn stub(params: Option<&str>) -> String {
+    match params {
+        Some(params) => PARAMS_STUB.replace("PARAMS", params),
+        None => STUB.to_string(),
+    }
+}
+
+/// Add a stub implementation of a day to `solutions` or `optimized` and register it,
+/// after the existing implementations of the day,
+/// or in front of them with `first` so that the runner, the integration tests
+/// and the benchmarks use it.
+pub fn new_day(day: usize, optimized: bool, first: bool) -> Result<(), String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module = if optimized { "optimized" } else { "solutions" };
+    let read = |path: &str| {
+        std::fs::read_to_string(root.join(path))
+            .map_err(|err| format!("couldn't read `{path}`: {err}"))
+    };
+    let write = |path: &str, contents: &str| {
+        std::fs::write(root.join(path), contents)
+            .map_err(|err| format!("couldn't write `{path}`: {err}"))
+    };
+
+    let file = format!("src/{module}/day{day:02}.rs");
+    if root.join(&file).exists() {
+        return Err(format!("`{file}` already exists"));
+    }
+    let list = format!("src/{module}.rs");
+    let modules = add_module(&read(&list)?, day);
+    let registry = read("src/registry.rs")?;
+    let params = params(&registry, day);
+    let registry = register(&registry, module, day, first)?;
+
+    write(&file, &stub(params.as_deref()))?;
+    write(&list, &modules)?;
+    write("src/registry.rs", &registry)?;
+    println!("Created {file}");
+    println!("Registered {module}::day{day:02} in {list} and src/registry.rs");
+    if !first {
+        println!("Move it to the front of its entry in src/registry.rs to use it by default");
+    }
+    Ok(())
+}
+
+/// Add `pub mod dayNN;` to a module list, keeping it sorted.
+fn add_module(list: &str, day: usize) -> String {
+    let line = format!("pub mod day{day:02};");
+    let mut lines: Vec<&str> = list.lines().collect();
+    let idx = lines.iter().position(|&l| l > line.as_str()).unwrap_or(lines.len());
+    lines.insert(idx, &line);
+    lines.iter().fold(String::new(), |mut out, line| {
+        writeln!(out, "{line}").unwrap();
+        out
+    })
+}
+
+/// Day and type of parameters of an entry of the `registry!` invocation, like
+/// `15 [Day15]: optimized::day15 (i32, u64), solutions::day15 (i32, u64);`
+fn entry(line: &str) -> Option<(usize, Option<&str>)> {
+    let head = line.trim_start().split_once(':')?.0;
+    let (day, params) = match head.split_once(' ') {
+        Some((day, params)) => (day, Some(params.strip_prefix('[')?.strip_suffix(']')?)),
+        None => (head, None),
+    };
+    Some((day.parse().ok()?, params))
+}
+
+/// Type of the parameters of a day in the `registry!` invocation, if it has some.
+fn params(registry: &str, day: usize) -> Option<String> {
+    let (_, params) = registry.lines().filter_map(entry).find(|&(d, _)| d == day)?;
+    params.map(String::from)
+}
+
+/// Add an implementation to the `registry!` invocation, after any existing implementations,
+/// or in front of them with `first` so that it becomes the chosen one.
+fn register(registry: &str, module: &str, day: usize, first: bool) -> Result<String, String> {
+    let implementation = format!("{module}::day{day:02} {ANSWERS}");
+    let entry_day = |line: &str| entry(line).map(|(day, _)| day);
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("registry! {"))
+        .ok_or("no `registry!` invocation in src/registry.rs")?;
+    let entries = start + 1
+        ..start + lines[start..].iter().position(|line| line == "}").ok_or("unclosed registry")?;
+
+    if let Some(idx) = entries.clone().find(|&idx| entry_day(&lines[idx]) == Some(day)) {
+        let line = &lines[idx];
+        if line.contains(&format!("{module}::day{day:02} ")) {
+            return Err(format!("{module}::day{day:02} is already registered"));
+        }
+        lines[idx] = if first {
+            let (head, rest) = line.split_once(": ").unwrap();
+            format!("{head}: {implementation}, {rest}")
+        } else {
+            format!("{}, {implementation};", line.trim_end().trim_end_matches(';'))
+        };
+    } else {
+        let idx = entries
+            .clone()
+            .find(|&idx| entry_day(&lines[idx]).is_some_and(|d| d > day))
+            .unwrap_or(entries.end);
+        lines.insert(idx, format!("    {day}: {implementation};"));
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modules() {
+        let list = "pub mod day01;\npub mod day13;\n";
+        assert_eq!(add_module(list, 6), "pub mod day01;\npub mod day06;\npub mod day13;\n");
+        assert_eq!(add_module(list, 25), "pub mod day01;\npub mod day13;\npub mod day25;\n");
+    }
+
+    #[test]
+    fn registry() {
+        let registry = "\
+#[rustfmt::skip]
+registry! {
+    1: solutions::day01 (u32, u32);
+    3: optimized::day03 (u32, u32);
+}
+";
+        assert_eq!(
+            register(registry, "optimized", 1, true).unwrap(),
+            registry.replace("1: ", "1: optimized::day01 (usize, usize), ")
+        );
+        assert_eq!(
+            register(registry, "optimized", 1, false).unwrap(),
+            registry.replace(
+                "(u32, u32);\n    3",
+                "(u32, u32), optimized::day01 (usize, usize);\n    3",
+            )
+        );
+        assert_eq!(
+            register(registry, "solutions", 2, false).unwrap(),
+            registry.replace("    3:", "    2: solutions::day02 (usize, usize);\n    3:")
+        );
+        assert!(register(registry, "solutions", 5, false).unwrap().ends_with(
+            "    5: solutions::day05 (usize, usize);\n}\n"
+        ));
+        assert!(register(registry, "optimized", 3, false).is_err());
+        assert!(register("", "optimized", 3, false).is_err());
+    }
+
+    #[test]
+    fn this_tree() {
+        // Every day exists already, so only missing optimized implementations can be added.
+        let registry = include_str!("registry.rs");
+        assert!(register(registry, "solutions", 16, false).is_err());
+        assert!(register(registry, "solutions", 22, false).is_err());
+        assert!(register(registry, "optimized", 2, true)
+            .unwrap()
+            .contains("    2: optimized::day02 (usize, usize), solutions::day02 (u32, u32);\n"));
+        assert!(register(registry, "optimized", 22, false).unwrap().contains(
+            "    22 [Day22]: solutions::day22 (usize, usize), optimized::day22 (usize, usize);\n"
+        ));
+    }
+
+    #[test]
+    fn params_day() {
+        let registry = include_str!("registry.rs");
+        assert_eq!(params(registry, 17).as_deref(), Some("Day17"));
+        assert_eq!(params(registry, 18), None);
+        // The stub has the signature that `registry!` calls for a day with parameters.
+        let stub = stub(Some("Day17"));
+        assert!(stub.contains("use crate::params::Day17;\n"));
+        assert!(stub.contains("pub fn run_with(input: &str, params: &Day17) -> (usize, usize)"));
+        assert!(stub.contains("run_with(input, &Day17::default())"));
+        assert!(super::stub(None).contains("pub fn run(input: &str)"));
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::output::Record;
+
+const HEADER: &str = "profile\tday\timplementation\ttime_ns";
+
+/// Times of a previous run, used as a baseline for later runs.
+///
+/// Stored as tab-separated lines of profile, day, implementation and time in nanoseconds.
+/// The time is the median time when timing multiple runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    times: HashMap<(String, usize, String), Duration>,
+}
+
+impl Snapshot {
+    /// Take the times of the records that ran to completion.
+    pub fn from_records(records: &[Record]) -> Self {
+        let times = records
+            .iter()
+            .filter_map(|record| {
+                let solved = record.outcome.as_ref().ok()?;
+                let key = (record.profile.clone(), record.day, record.implementation.to_owned());
+                Some((key, solved.stats.median))
+            })
+            .collect();
+        Self { times }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut times = HashMap::new();
+        for (idx, line) in s.lines().enumerate() {
+            if line.is_empty() || (idx == 0 && line == HEADER) {
+                continue;
+            }
+            let invalid = || format!("invalid snapshot line {}: `{line}`", idx + 1);
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [profile, day, implementation, nanos] = fields[..] else {
+                return Err(invalid());
+            };
+            let day = day.parse().map_err(|_| invalid())?;
+            let nanos = nanos.parse().map_err(|_| invalid())?;
+            let key = (profile.to_owned(), day, implementation.to_owned());
+            times.insert(key, Duration::from_nanos(nanos));
+        }
+        Ok(Self { times })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let s = std::fs::read_to_string(path)
+            .map_err(|err| format!("couldn't read snapshot `{path}`: {err}"))?;
+        Self::parse(&s)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.to_string())
+            .map_err(|err| format!("couldn't write snapshot `{path}`: {err}"))
+    }
+
+    pub fn get(&self, profile: &str, day: usize, implementation: &str) -> Option<Duration> {
+        self.times.get(&(profile.to_owned(), day, implementation.to_owned())).copied()
+    }
+}
+
+impl std::fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut entries: Vec<_> = self.times.iter().collect();
+        entries.sort();
+        let mut out = format!("{HEADER}\n");
+        for ((profile, day, implementation), time) in entries {
+            writeln!(out, "{profile}\t{day}\t{implementation}\t{}", time.as_nanos())?;
+        }
+        f.write_str(&out)
+    }
+}
+
+/// Relative change from `old` to `new` in percent,
+/// or `None` if `old` is too short to compare to.
+pub fn change(old: Duration, new: Duration) -> Option<f64> {
+    if old.is_zero() {
+        return None;
+    }
+    Some((new.as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0)
+}
+
+/// Parse a percentage such as `10` or `2.5%`.
+pub fn parse_threshold(s: &str) -> Result<f64, String> {
+    match s.strip_suffix('%').unwrap_or(s).parse::<f64>() {
+        Ok(threshold) if threshold >= 0.0 => Ok(threshold),
+        _ => Err(format!("invalid threshold `{s}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Solved;
+    use crate::timing::Stats;
+    use aoc2022::answer::Answer;
+    use aoc2022::profile::Expected;
+
+    #[test]
+    fn round_trip() {
+        let record = |day, nanos| Record {
+            profile: String::from("github"),
+            day,
+            implementation: "optimized::day16",
+            expected: Expected::default(),
+            outcome: Ok(Solved {
+                answers: [Answer::None, Answer::None],
+                stats: Stats::from_samples(&mut [Duration::from_nanos(nanos)]),
+                parallel: false,
+            }),
+        };
+        let snapshot = Snapshot::from_records(&[record(16, 1500), record(9, 20)]);
+        let saved = snapshot.to_string();
+        let lines: Vec<&str> = saved.lines().collect();
+        assert_eq!(
+            lines,
+            [HEADER, "github\t9\toptimized::day16\t20", "github\t16\toptimized::day16\t1500"]
+        );
+        let loaded = Snapshot::parse(&saved).unwrap();
+        assert_eq!(loaded, snapshot);
+        assert_eq!(loaded.get("github", 16, "optimized::day16"), Some(Duration::from_nanos(1500)));
+        assert_eq!(loaded.get("github", 16, "solutions::day16"), None);
+        assert!(Snapshot::parse("github\t16\t1500").is_err());
+    }
+
+    #[test]
+    fn changes() {
+        let ms = Duration::from_millis;
+        assert_eq!(change(ms(200), ms(250)), Some(25.0));
+        assert_eq!(change(ms(200), ms(100)), Some(-50.0));
+        assert_eq!(change(ms(0), ms(100)), None);
+        assert_eq!(change(ms(0), ms(0)), None);
+        assert_eq!(parse_threshold("2.5%"), Ok(2.5));
+        assert_eq!(parse_threshold("10"), Ok(10.0));
+        assert!(parse_threshold("-5").is_err());
+    }
+}
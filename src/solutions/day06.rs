@@ -8,10 +8,19 @@ pub fn run(input: &str) -> (usize, usize) {
 
 /// Sliding window, generate a bitset and check its length.
 fn task(input: &str, length: usize) -> usize {
-    length + input
+    input
         .as_bytes()
         .windows(length)
         .position(|window| HashSet::<&u8>::from_iter(window.iter()).len() == length)
         .expect("no packet marker detected!")
         + length
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test() {
+        assert_eq!(super::run("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), (7, 19));
+        assert_eq!(super::run("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), (10, 29));
+    }
+}
@@ -1,5 +1,8 @@
 use std::fmt::{Display, Write};
 
+use crate::answer::{Answer, Bitmap};
+use crate::ocr::{self, OcrError};
+
 pub fn run(input: &str) -> (i32, Res2) {
     let input = input.as_bytes();
 
@@ -64,16 +67,32 @@ impl Res2 {
     fn blit(&mut self, idx: u16) {
         self.data[(idx >> Self::BITSHIFT) as usize] |= 1 << (idx & Self::MASK);
     }
+
+    fn pixel(&self, idx: u16) -> bool {
+        self.data[(idx >> Self::BITSHIFT) as usize] & (1 << (idx & Self::MASK)) != 0
+    }
+
+    pub fn bitmap(&self) -> Bitmap {
+        Bitmap::new(40, (0..240).map(|idx| self.pixel(idx)).collect())
+    }
+
+    /// The eight letters shown on the CRT.
+    pub fn text(&self) -> Result<String, OcrError> {
+        ocr::read(&self.bitmap())
+    }
+}
+
+impl From<Res2> for Answer {
+    fn from(res: Res2) -> Self {
+        Answer::Bitmap(res.bitmap())
+    }
 }
 
 impl Display for Res2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..6 {
             for x in 0..40 {
-                let idx = x + y * 40;
-                let bit =
-                    self.data[(idx >> Self::BITSHIFT) as usize] & (1 << (idx & Self::MASK)) != 0;
-                let c = if bit { '█' } else { '░' };
+                let c = if self.pixel(x + y * 40) { '█' } else { '░' };
                 f.write_char(c)?;
             }
             f.write_char('\n')?;
@@ -234,7 +253,12 @@ mod test {
             noop\n\
             noop\n\
             noop";
-        let (res1, _) = run(input);
+        let (res1, res2) = run(input);
         assert_eq!(res1, 13140);
+        // The example draws stripes rather than letters.
+        let Err(OcrError::Unknown { positions, .. }) = res2.text() else {
+            panic!("expected unknown glyphs");
+        };
+        assert_eq!(positions, (0..8).collect::<Vec<_>>());
     }
 }
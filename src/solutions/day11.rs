@@ -108,13 +108,13 @@ where
             for item in monkey.items.iter() {
                 monkey.inspection_count += 1;
                 let item = worry_reduction(monkey.op.apply(*item));
-                let target = if item % monkey.test == 0 {
+                let target = if item.is_multiple_of(monkey.test) {
                     monkey.pos
                 } else {
                     monkey.neg
                 };
                 unsafe {
-                    (*monkeys_target)[target].items.push(item);
+                    (&mut *monkeys_target)[target].items.push(item);
                 }
             }
             monkey.items.clear();
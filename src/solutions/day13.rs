@@ -55,11 +55,11 @@ impl Ord for List {
                 elem_cmp.unwrap_or(length_cmp)
             }
             (List::List(s), o) => s
-                .get(0)
+                .first()
                 .map_or(Ordering::Less, |s0| s0.cmp(o))
                 .then(s.len().cmp(&1)),
             (s, List::List(o)) => o
-                .get(0)
+                .first()
                 .map_or(Ordering::Greater, |o0| s.cmp(o0))
                 .then(1.cmp(&o.len())),
         }
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::params::Day15;
+
 struct Diamond {
     x: i32,
     y: i32,
@@ -34,7 +36,7 @@ impl Diamond {
         dist <= self.size
     }
 
-    fn boundary(&self) -> Boundary {
+    fn boundary(&self) -> Boundary<'_> {
         Boundary {
             diamond: self,
             progress: 0,
@@ -97,12 +99,9 @@ impl Slice {
     }
 }
 
-pub fn run(input: &str) -> (i32, u64) {
-    #[cfg(test)]
-    let line = 10;
-    #[cfg(not(test))]
-    let line = 2_000_000;
-    let search_space = line * 2;
+pub fn run_with(input: &str, params: &Day15) -> (i32, u64) {
+    let line = params.row;
+    let search_space = params.max;
 
     // Parse
     let sensors: Vec<((i32, i32), (i32, i32))> = input
@@ -170,6 +169,8 @@ pub fn run(input: &str) -> (i32, u64) {
 
 #[cfg(test)]
 mod tests {
+    use crate::params::Day15;
+
     #[test]
     fn test() {
         let input = "\
@@ -188,6 +189,6 @@ mod tests {
             Sensor at x=14, y=3: closest beacon is at x=15, y=3\n\
             Sensor at x=20, y=1: closest beacon is at x=15, y=3\n\
         ";
-        assert_eq!(super::run(input), (26, 56000011));
+        assert_eq!(super::run_with(input, &Day15 { row: 10, max: 20 }), (26, 56000011));
     }
 }
@@ -1,6 +1,7 @@
 use itertools::Itertools;
 use std::ops::Range;
 
+use crate::params::Day16;
 use crate::util::dfs::Dfs;
 use crate::util::graph::{GraphImpl, HashGraph, VecGraph};
 use crate::util::grid::{pos, Grid};
@@ -27,13 +28,13 @@ struct State1<'a> {
 }
 
 impl<'a> State1<'a> {
-    fn new(valves: &'a Vec<u64>, costs: &'a Grid<u64>, node: usize) -> Self {
+    fn new(valves: &'a Vec<u64>, costs: &'a Grid<u64>, node: usize, steps_left: u64) -> Self {
         Self {
             valves,
             costs,
             start: node,
             node,
-            steps_left: 30,
+            steps_left,
             score: 0,
         }
     }
@@ -94,19 +95,13 @@ struct Actor {
 }
 
 impl<'a> State2<'a> {
-    fn new(valves: &'a Vec<u64>, costs: &'a Grid<u64>, node: usize) -> Self {
+    fn new(valves: &'a Vec<u64>, costs: &'a Grid<u64>, node: usize, steps_left: u64) -> Self {
         Self {
             valves,
             costs,
             actors: [
-                Actor {
-                    node,
-                    steps_left: 26,
-                },
-                Actor {
-                    node,
-                    steps_left: 26,
-                },
+                Actor { node, steps_left },
+                Actor { node, steps_left },
             ],
             score: 0,
         }
@@ -169,7 +164,7 @@ impl<'a> Dfs for State2<'a> {
     }
 }
 
-pub fn run(input: &str) -> (u64, u64) {
+pub fn run_with(input: &str, params: &Day16) -> (u64, u64) {
     // Parse into a graph
     let mut valves: HashGraph<&str, u64> = input.lines().map(|l| parse_valve(l).unwrap()).collect();
 
@@ -208,8 +203,9 @@ pub fn run(input: &str) -> (u64, u64) {
         .position(|&x| x == valves.start)
         .unwrap();
 
-    let mut dfs_state1 = State1::new(&new_valves, &cost_grid, dfs_start);
-    let mut dfs_state2 = State2::new(&new_valves, &cost_grid, dfs_start);
+    let steps = (params.minutes_1.into(), params.minutes_2.into());
+    let mut dfs_state1 = State1::new(&new_valves, &cost_grid, dfs_start, steps.0);
+    let mut dfs_state2 = State2::new(&new_valves, &cost_grid, dfs_start, steps.1);
     let res1 = dfs_state1.dfs();
     let res2 = dfs_state2.dfs();
     (res1, res2)
@@ -230,6 +226,6 @@ mod tests {
             Valve HH has flow rate=22; tunnel leads to valve GG\n\
             Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
             Valve JJ has flow rate=21; tunnel leads to valve II";
-        assert_eq!(super::run(input), (1651, 1707));
+        assert_eq!(super::run_with(input, &Default::default()), (1651, 1707));
     }
 }
@@ -3,6 +3,8 @@ use std::{
     fmt::{Display, Write},
 };
 
+use crate::params::Day17;
+
 // Rows are 1-byte bit vectors.
 // Rightmost cell is lowest bit.
 // Highest bit is unused.
@@ -104,7 +106,7 @@ impl Tower {
 
             if self.rows.iter().take_while(|&&row| row != 0).count() <= 8 {
                 let bottom = u64::from_le_bytes([
-                    *self.rows.get(0).unwrap_or(&0),
+                    *self.rows.front().unwrap_or(&0),
                     *self.rows.get(1).unwrap_or(&0),
                     *self.rows.get(2).unwrap_or(&0),
                     *self.rows.get(3).unwrap_or(&0),
@@ -164,19 +166,19 @@ impl Display for Tower {
     }
 }
 
-pub fn run(input: &str) -> (usize, usize) {
+pub fn run_with(input: &str, params: &Day17) -> (usize, usize) {
     let mut wind = input.trim().bytes().enumerate().cycle();
     let mut rocks = ROCKS.iter().cycle();
     let mut tower = Tower::new();
 
     let mut i = 0;
-    while i < 2022 {
+    while i < params.rocks_1 {
         i += 1;
         tower.place(*rocks.next().unwrap(), i, &mut wind);
     }
     let res1 = tower.height();
 
-    let limit = 1_000_000_000_000;
+    let limit = params.rocks_2;
     while i < limit {
         i += 1;
         if let Some((pentry, entry)) = tower.place(*rocks.next().unwrap(), i, &mut wind) {
@@ -196,11 +198,21 @@ pub fn run(input: &str) -> (usize, usize) {
     (res1, tower.height())
 }
 
+/// Whether the tower repeats within the first `rocks` rocks, which part 2 relies on.
+/// That only happens once rocks fill complete rows, so that the rows below can be forgotten.
+pub fn repeats_within(input: &str, rocks: usize) -> bool {
+    let mut wind = input.trim().bytes().enumerate().cycle();
+    let mut tower = Tower::new();
+    (1..=rocks)
+        .zip(ROCKS.iter().cycle())
+        .any(|(i, rock)| tower.place(*rock, i, &mut wind).is_some())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn test() {
         let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-        assert_eq!(super::run(input), (3068, 1514285714288));
+        assert_eq!(super::run_with(input, &Default::default()), (3068, 1514285714288));
     }
 }
@@ -6,6 +6,8 @@ use std::{
 use fxhash::FxHashSet;
 use itertools::Itertools;
 
+use crate::params::Day19;
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Default)]
 struct Currency {
     ore: u8,
@@ -192,7 +194,7 @@ struct State {
     robots: Currency,
 }
 
-pub fn run(input: &str) -> (u64, u64) {
+pub fn run_with(input: &str, params: &Day19) -> (u64, u64) {
     let mut blueprints = input
         .lines()
         .map(|line| Blueprint::parse(line).unwrap())
@@ -200,7 +202,7 @@ pub fn run(input: &str) -> (u64, u64) {
 
     let mut res1 = 0;
     for blueprint in &mut blueprints {
-        let states = blueprint.simulate(24);
+        let states = blueprint.simulate(params.minutes_1.into());
         let max_geodes = states
             .iter()
             .map(|state| state.inventory.geode)
@@ -210,9 +212,9 @@ pub fn run(input: &str) -> (u64, u64) {
     }
 
     let mut res2 = 1;
-    let upper = blueprints.len().min(3);
+    let upper = blueprints.len().min(params.blueprints_2);
     for blueprint in &mut blueprints[0..upper] {
-        let states = blueprint.simulate(32);
+        let states = blueprint.simulate(params.minutes_2.into());
         let max_geodes = states
             .iter()
             .map(|state| state.inventory.geode)
@@ -229,12 +231,12 @@ mod tests {
     #[test]
     fn test() {
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.\nBlueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
-        assert_eq!(super::run(input), (33, 56 * 62));
+        assert_eq!(super::run_with(input, &Default::default()), (33, 56 * 62));
     }
 
     #[test]
     fn test_tiny() {
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
-        assert_eq!(super::run(input), (9, 56));
+        assert_eq!(super::run_with(input, &Default::default()), (9, 56));
     }
 }
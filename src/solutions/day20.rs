@@ -119,7 +119,7 @@ impl BlockSeq {
         shift += offset;
         while shift > self.blocks[block].len() {
             let group = block / Self::GROUP;
-            if block % Self::GROUP == 0 && shift >= self.groups[group] {
+            if block.is_multiple_of(Self::GROUP) && shift >= self.groups[group] {
                 shift -= self.groups[group];
                 block += Self::GROUP;
                 if block >= self.blocks.len() {
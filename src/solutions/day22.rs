@@ -6,16 +6,13 @@ use std::{
 
 use itertools::Itertools;
 
+use crate::params::Day22;
 use crate::util::{
     graph::GraphImpl,
     grid::{pos, Dir4, Grid, Pos, Rot},
 };
 
-pub fn run(input: &str) -> (usize, usize) {
-    run_size::<50>(input)
-}
-
-pub fn run_size<const N: usize>(input: &str) -> (usize, usize) {
+pub fn run_with(input: &str, params: &Day22) -> (usize, usize) {
     let (map, insts) = input.trim_end().split_once("\n\n").unwrap();
     let grid = Grid::parse_default(map, Cell::Nothing, |_, c| match c {
         '#' => Cell::Wall,
@@ -25,14 +22,13 @@ pub fn run_size<const N: usize>(input: &str) -> (usize, usize) {
     });
     let flat_map = FlatMap::new(&grid);
     let res1 = task(insts, flat_map);
-    let cube_map = CubeMap::<N>::new(&grid);
+    let cube_map = CubeMap::new(&grid, params.cube_size);
     let res2 = task(insts, cube_map);
     (res1, res2)
 }
 
 trait MapRep: Index<Self::Pos, Output = bool> {
     type Pos: WalkPos;
-    fn new(map: &Grid<Cell>) -> Self;
     fn start(&self) -> Self::Pos;
     fn step_fwd(&self, p: Self::Pos) -> Self::Pos;
     fn result(&self, p: Self::Pos) -> (Pos, Dir4);
@@ -132,9 +128,7 @@ impl Index<FlatPos> for FlatMap {
     }
 }
 
-impl MapRep for FlatMap {
-    type Pos = FlatPos;
-
+impl FlatMap {
     fn new(map: &Grid<Cell>) -> Self {
         let wrap_bounds: WrapBounds = map.clone().into();
         Self {
@@ -142,6 +136,10 @@ impl MapRep for FlatMap {
             wrap_bounds,
         }
     }
+}
+
+impl MapRep for FlatMap {
+    type Pos = FlatPos;
 
     fn start(&self) -> Self::Pos {
         let pos = pos(self.wrap_bounds.x_bounds[0].0, 0);
@@ -252,12 +250,13 @@ impl std::fmt::Debug for Face {
     }
 }
 
-struct CubeMap<const N: usize> {
+struct CubeMap {
+    size: usize,
     start: usize,
     faces: [Face; 6],
 }
 
-impl<const N: usize> Index<CubePos> for CubeMap<N> {
+impl Index<CubePos> for CubeMap {
     type Output = bool;
 
     fn index(&self, index: CubePos) -> &Self::Output {
@@ -265,18 +264,16 @@ impl<const N: usize> Index<CubePos> for CubeMap<N> {
     }
 }
 
-impl<const N: usize> MapRep for CubeMap<N> {
-    type Pos = CubePos;
-
-    fn new(map: &Grid<Cell>) -> Self {
+impl CubeMap {
+    fn new(map: &Grid<Cell>, n: usize) -> Self {
         // Collect faces.
         let mut faces = HashMap::new();
         let mut start = None;
-        for y in 0..(map.height() / N) {
-            for x in 0..(map.width() / N) {
-                let corner = pos(x * N, y * N);
+        for y in 0..(map.height() / n) {
+            for x in 0..(map.width() / n) {
+                let corner = pos(x * n, y * n);
                 if map[corner] != Cell::Nothing {
-                    faces.insert(pos(x, y), map.crop_area(corner, corner + pos(N, N)));
+                    faces.insert(pos(x, y), map.crop_area(corner, corner + pos(n, n)));
                     if start.is_none() {
                         start = Some(pos(x, y));
                     }
@@ -352,12 +349,16 @@ impl<const N: usize> MapRep for CubeMap<N> {
             .map(|(_, &grid_pos, surface, sides)| Face {
                 surface: surface.map(|cell| cell == &Cell::Air),
                 sides,
-                grid_pos: pos(grid_pos.x * N, grid_pos.y * N),
+                grid_pos: pos(grid_pos.x * n, grid_pos.y * n),
             })
             .collect_vec();
         let faces = faces.try_into().unwrap();
-        Self { faces, start }
+        Self { size: n, faces, start }
     }
+}
+
+impl MapRep for CubeMap {
+    type Pos = CubePos;
 
     fn start(&self) -> CubePos {
         CubePos {
@@ -368,11 +369,12 @@ impl<const N: usize> MapRep for CubeMap<N> {
     }
 
     fn step_fwd(&self, mut p: CubePos) -> CubePos {
-        p.pos += pos(N, N);
+        let n = self.size;
+        p.pos += pos(n, n);
         p.pos = p.pos.step(p.dir);
-        let overstep = p.pos.x == N - 1 || p.pos.x == N + N || p.pos.y == N - 1 || p.pos.y == N + N;
-        p.pos.x %= N;
-        p.pos.y %= N;
+        let overstep = p.pos.x == n - 1 || p.pos.x == n + n || p.pos.y == n - 1 || p.pos.y == n + n;
+        p.pos.x %= n;
+        p.pos.y %= n;
         if overstep {
             // retrieve new face and coordinate
             let (new_face, new_orient) = self.faces[p.face].sides[p.dir.to_idx()];
@@ -385,17 +387,17 @@ impl<const N: usize> MapRep for CubeMap<N> {
                 Dir4::E => {
                     p.dir = p.dir.rotate(Rot::L);
                     p.pos = p.pos.swap_xy();
-                    p.pos.y = N - 1 - p.pos.y;
+                    p.pos.y = n - 1 - p.pos.y;
                 }
                 Dir4::W => {
                     p.dir = p.dir.rotate(Rot::R);
                     p.pos = p.pos.swap_xy();
-                    p.pos.x = N - 1 - p.pos.x;
+                    p.pos.x = n - 1 - p.pos.x;
                 }
                 Dir4::S => {
                     p.dir = p.dir.flip();
-                    p.pos.x = N - 1 - p.pos.x;
-                    p.pos.y = N - 1 - p.pos.y;
+                    p.pos.x = n - 1 - p.pos.x;
+                    p.pos.y = n - 1 - p.pos.y;
                 }
             }
         }
@@ -409,6 +411,8 @@ impl<const N: usize> MapRep for CubeMap<N> {
 
 #[cfg(test)]
 mod tests {
+    use crate::params::Day22;
+
     #[test]
     fn test() {
         let input = "        ...#
@@ -426,6 +430,6 @@ mod tests {
 
 10R5L5R10L4R5L5\n\
         ";
-        assert_eq!(super::run_size::<4>(input), (6032, 5031));
+        assert_eq!(super::run_with(input, &Day22 { cube_size: 4 }), (6032, 5031));
     }
 }
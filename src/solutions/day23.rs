@@ -49,13 +49,13 @@ pub fn run(input: &str) -> (u64, u64) {
     let mut res1 = 0;
     loop {
         for (elf, target) in &mut elves {
-            if grid[pos(elf.x + 1, elf.y + 0)] != Cell::Elf
+            if grid[pos(elf.x + 1, elf.y)] != Cell::Elf
                 && grid[pos(elf.x + 1, elf.y - 1)] != Cell::Elf
-                && grid[pos(elf.x + 0, elf.y - 1)] != Cell::Elf
+                && grid[pos(elf.x, elf.y - 1)] != Cell::Elf
                 && grid[pos(elf.x - 1, elf.y - 1)] != Cell::Elf
-                && grid[pos(elf.x - 1, elf.y + 0)] != Cell::Elf
+                && grid[pos(elf.x - 1, elf.y)] != Cell::Elf
                 && grid[pos(elf.x - 1, elf.y + 1)] != Cell::Elf
-                && grid[pos(elf.x - 0, elf.y + 1)] != Cell::Elf
+                && grid[pos(elf.x, elf.y + 1)] != Cell::Elf
                 && grid[pos(elf.x + 1, elf.y + 1)] != Cell::Elf
             {
                 continue;
@@ -1,171 +1,93 @@
-use std::ops::Index;
-use std::ops::IndexMut;
-
-use itertools::Itertools;
-
-use crate::util::{graph::*, grid::*, grid3d, pathfinding::bfs};
-
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
-struct TimePos {
-    pos: grid3d::Pos,
-}
-
-struct Blizzards<T> {
-    width: usize,
-    height: usize,
-    u: Grid<bool>,
-    d: Grid<bool>,
-    l: Grid<bool>,
-    r: Grid<bool>,
-    nodes: grid3d::Grid<T>,
-}
-
-impl<T> Blizzards<T> {
-    fn valid_pos(&self, p: &TimePos) -> bool {
-        let d = self.u[pos(p.pos.x, (p.pos.y + p.pos.z) % self.height)];
-        let r = self.l[pos((p.pos.x + p.pos.z) % self.width, p.pos.y)];
-        let u = self.d[pos(
-            p.pos.x,
-            (p.pos.y + (self.height - p.pos.z % self.height)) % self.height,
-        )];
-        let l = self.r[pos(
-            (p.pos.x + (self.width - p.pos.z % self.width)) % self.width,
-            p.pos.y,
-        )];
-        p.pos.z < self.nodes.depth() && u && d && r && l
+use crate::util::{
+    grid::{self, Grid},
+    grid3d::{self, ipos, pos, IPos, Neighborhood},
+    pathfinding::bfs,
+};
+
+/// Moving or waiting, each taking a minute.
+const MOVES: [IPos; 5] = [
+    ipos(0, 0, 1),
+    ipos(1, 0, 1),
+    ipos(-1, 0, 1),
+    ipos(0, 1, 1),
+    ipos(0, -1, 1),
+];
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
 }
 
-impl<T> Index<TimePos> for Blizzards<T> {
-    type Output = T;
-
-    fn index(&self, index: TimePos) -> &Self::Output {
-        //println!("{index:?}");
-        &self.nodes[index.pos]
-    }
-}
-
-impl<T> IndexMut<TimePos> for Blizzards<T> {
-    fn index_mut(&mut self, index: TimePos) -> &mut Self::Output {
-        &mut self.nodes[index.pos]
-    }
+pub fn run(input: &str) -> (usize, usize) {
+    let (free, entrance, exit) = parse(input);
+    let there = trip(&free, entrance, exit, 0).unwrap();
+    let back = there + trip(&free, exit, entrance, there).unwrap();
+    let again = back + trip(&free, entrance, exit, back).unwrap();
+    (there, again)
 }
 
-impl<T> Graph<T> for Blizzards<T> {}
-impl<T> GraphImpl<T> for Blizzards<T> {
-    type Node = TimePos;
-
-    type Neighbors = std::vec::IntoIter<TimePos>;
-    fn neighbors(&self, handle: Self::Node) -> Self::Neighbors {
-        let mut candidates = self
-            .u
-            .neighbors(pos(handle.pos.x, handle.pos.y))
-            .map(|pos| TimePos {
-                pos: grid3d::Pos {
-                    x: pos.x,
-                    y: pos.y,
-                    z: handle.pos.z + 1,
-                },
-            })
-            .collect_vec();
-        candidates.push(TimePos {
-            pos: grid3d::Pos {
-                x: handle.pos.x,
-                y: handle.pos.y,
-                z: handle.pos.z + 1,
-            },
-        });
-        candidates.retain(|p| self.valid_pos(p));
-        candidates.into_iter()
-    }
-
-    // NOTE: Not implemented
-    type AllNodes = std::vec::IntoIter<TimePos>;
-    fn nodes(&self) -> Self::AllNodes {
-        vec![].into_iter()
-    }
-
-    type Map<U> = Blizzards<U>;
-    fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Self::Map<U> {
-        let new_nodes = self.nodes.map(f);
-        Blizzards {
-            width: self.width,
-            height: self.height,
-            u: self.u.clone(),
-            d: self.d.clone(),
-            l: self.l.clone(),
-            r: self.r.clone(),
-            nodes: new_nodes,
+/// Whether each cell of the valley, walls included, is free at each minute,
+/// along with the entrance and the exit.
+fn parse(input: &str) -> (grid3d::Grid<bool>, (usize, usize), (usize, usize)) {
+    let valley = Grid::parse(input, |_, c| c);
+    let (width, height) = (valley.width() - 2, valley.height() - 2);
+    // The blizzards are back where they started after this many minutes,
+    // so the valley over time wraps around in z.
+    let period = width / gcd(width, height) * height;
+
+    let mut free = grid3d::Grid::new_filled(valley.width(), valley.height(), period, false);
+    for t in 0..period {
+        for y in 0..valley.height() {
+            for x in 0..valley.width() {
+                free[pos(x, y, t)] = valley[grid::pos(x, y)] != '#';
+            }
         }
     }
-}
-
-pub fn run(input: &str) -> (usize, usize) {
-    let width = input.lines().next().unwrap().len() - 2;
-    let height = input.lines().count() - 2;
-    let mut u = Grid::new_filled(width, height, true);
-    let mut d = Grid::new_filled(width, height, true);
-    let mut r = Grid::new_filled(width, height, true);
-    let mut l = Grid::new_filled(width, height, true);
-    drop(Grid::parse(input, |p, c| {
-        if p.x > 0 && p.y > 0 {
-            let bpos = pos(p.x - 1, p.y - 1);
-            match c {
-                '^' => u[bpos] = false,
-                'v' => d[bpos] = false,
-                '<' => l[bpos] = false,
-                '>' => r[bpos] = false,
-                '.' => (),
-                '#' => (),
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = match valley[grid::pos(x + 1, y + 1)] {
+                '>' => (1, 0),
+                '<' => (width - 1, 0),
+                'v' => (0, 1),
+                '^' => (0, height - 1),
+                '.' => continue,
                 c => panic!("unrecognized character '{c}'"),
             };
+            for t in 0..period {
+                let p = pos(1 + (x + dx * t) % width, 1 + (y + dy * t) % height, t);
+                free[p] = false;
+            }
         }
-    }));
-    let blizzards: Blizzards<()> = Blizzards {
-        width,
-        height,
-        u,
-        d,
-        l,
-        r,
-        nodes: grid3d::Grid::new_filled(width, height, 10000, ()), // TODO: Magic number
-    };
-
-    let cost = 2 + bfs(
-        &blizzards,
-        TimePos {
-            pos: grid3d::pos(0, 0, 1),
-        },
-        |_, _| true,
-        |_, p| p.pos == grid3d::pos(width - 1, height - 1, p.pos.z),
-    )
-    .unwrap();
-    //let cost_back = 2 + bfs(
-    //    &blizzards,
-    //    TimePos {
-    //        pos: grid3d::pos(width - 1, height - 1, cost + 6),
-    //    },
-    //    |_, _| true,
-    //    |_, p| p.pos == grid3d::pos(0, 0, p.pos.z),
-    //)
-    //.unwrap();
-    //let cost_back_again = 2 + bfs(
-    //    &blizzards,
-    //    TimePos {
-    //        pos: grid3d::pos(0, 0, cost + cost_back + 2),
-    //    },
-    //    |_, _| true,
-    //    |_, p| p.pos == grid3d::pos(width - 1, height - 1, p.pos.z),
-    //)
-    //.unwrap();
+    }
+    // Wrapping around in x or y leads into the walls, so only the wrapping in time is used.
+    let free = free.with_neighborhood(Neighborhood::stencil(&MOVES).wrapping());
+    (free, (1, 0), (width, height + 1))
+}
 
-    (cost, 0)
+/// Minutes it takes to get from one cell to another, setting off at minute `t`.
+fn trip(
+    free: &grid3d::Grid<bool>,
+    (x, y): (usize, usize),
+    to: (usize, usize),
+    t: usize,
+) -> Option<usize> {
+    let start = pos(x, y, t % free.depth());
+    bfs(free, start, |_, n| free[n], |_, p: grid3d::Pos| (p.x, p.y) == to)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    /// Minutes to the exit, for valleys that don't allow the way back.
+    fn there(input: &str) -> Option<usize> {
+        let (free, entrance, exit) = parse(input);
+        trip(&free, entrance, exit, 0)
+    }
+
     #[test]
     fn no_blizzards() {
         let input = "\
@@ -176,7 +98,7 @@ mod test {
 #......#
 ######.#\
 ";
-        assert_eq!(run(input).0, 10);
+        assert_eq!(there(input), Some(10));
     }
 
     #[test]
@@ -189,7 +111,7 @@ mod test {
 #.vvv.v#
 ######.#\
 ";
-        assert_eq!(run(input).0, 10);
+        assert_eq!(there(input), Some(10));
     }
 
     #[test]
@@ -202,7 +124,7 @@ mod test {
 #>>.>>>#
 ######.#\
 ";
-        assert_eq!(run(input).0, 10);
+        assert_eq!(there(input), Some(10));
     }
 
     #[test]
@@ -215,7 +137,7 @@ mod test {
 #......#
 ######.#\
 ";
-        assert_eq!(run(input).0, 10);
+        assert_eq!(there(input), Some(10));
     }
 
     #[test]
@@ -228,7 +150,7 @@ mod test {
 #.^.^^.#
 ######.#\
 ";
-        assert_eq!(run(input).0, 10);
+        assert_eq!(there(input), Some(10));
     }
 
     #[test]
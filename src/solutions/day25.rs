@@ -31,10 +31,10 @@ fn print_snafu(mut number: i64) -> String {
     result.chars().rev().collect()
 }
 
-pub fn run(input: &str) -> (String, &'static str) {
+pub fn run(input: &str) -> (String, ()) {
     let sum: i64 = input.lines().map(read_snafu).sum();
     let res1 = print_snafu(sum);
-    (res1, "")
+    (res1, ())
 }
 
 
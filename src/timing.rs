@@ -0,0 +1,130 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How many times to run each solution when timing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    /// Minimum number of runs.
+    pub runs: usize,
+    /// Keep running until at least this much time has been spent in total.
+    pub min_time: Duration,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self { runs: 1, min_time: Duration::ZERO }
+    }
+}
+
+impl Repeat {
+    /// Whether more than a single run may be performed.
+    pub fn is_statistical(&self) -> bool {
+        self.runs > 1 || !self.min_time.is_zero()
+    }
+}
+
+/// Summary of the execution times of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort_unstable();
+        let runs = samples.len();
+        let median = if runs % 2 == 1 {
+            samples[runs / 2]
+        } else {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = if runs > 1 {
+            let mean = mean.as_nanos() as f64;
+            let squares: f64 = samples.iter().map(|s| (s.as_nanos() as f64 - mean).powi(2)).sum();
+            squares / (runs - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+/// Run `task` according to `repeat`, returning the result of the first run.
+pub fn measure<R>(repeat: Repeat, mut task: impl FnMut() -> R) -> (R, Stats) {
+    let start = Instant::now();
+    let result = task();
+    let mut samples = vec![start.elapsed()];
+    while samples.len() < repeat.runs || start.elapsed() < repeat.min_time {
+        let run_start = Instant::now();
+        black_box(task());
+        samples.push(run_start.elapsed());
+    }
+    (result, Stats::from_samples(&mut samples))
+}
+
+/// Parse a duration such as `2s`, `500ms`, `250us` or `100ns`, defaulting to seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().map_err(|_| format!("invalid duration `{s}`"))?;
+    let nanos_per_unit = match unit {
+        "" | "s" => 1e9,
+        "ms" => 1e6,
+        "us" | "µs" => 1e3,
+        "ns" => 1.0,
+        _ => return Err(format!("invalid duration unit in `{s}`")),
+    };
+    Ok(Duration::from_nanos((value * nanos_per_unit).round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ns = Duration::from_nanos;
+        let mut samples = [ns(40), ns(10), ns(30), ns(20)];
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, ns(10));
+        assert_eq!(stats.median, ns(25));
+        assert_eq!(stats.mean, ns(25));
+        // Sample standard deviation of 10, 20, 30, 40 is ~12.91.
+        assert_eq!(stats.std_dev.as_nanos(), 12);
+        let single = Stats::from_samples(&mut [ns(7)]);
+        assert_eq!((single.median, single.mean, single.std_dev), (ns(7), ns(7), ns(0)));
+    }
+
+    #[test]
+    fn repeat() {
+        let mut count = 0;
+        let (first, stats) = measure(Repeat { runs: 5, min_time: Duration::ZERO }, || {
+            count += 1;
+            count
+        });
+        assert_eq!((first, stats.runs, count), (1, 5, 5));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250us"), Ok(Duration::from_micros(250)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("3h").is_err());
+    }
+}
@@ -1,7 +1,11 @@
+pub mod bitboard;
 pub mod cycles;
 pub mod dfs;
 pub mod graph;
 pub mod grid;
 pub mod grid3d;
+pub mod parse;
 pub mod pathfinding;
 pub mod queue;
+pub mod rng;
+pub mod sparse_grid;
@@ -0,0 +1,243 @@
+use std::{
+    fmt::{Display, Write},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
+
+use crate::util::grid::{pos, Dir8, Pos};
+
+/// A grid of bits of any size, for cellular automata that update all cells at once
+/// with shifts and boolean operations.
+///
+/// Each row is stored in as many `u64` words as its width needs, with x=0 as the lowest bit.
+/// The bits beyond the width are always kept clear, so shifts never wrap around the edges.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bitboard {
+    data: Vec<u64>,
+    width: usize,
+    height: usize,
+    /// Words per row.
+    stride: usize,
+}
+
+impl Bitboard {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+        Self {
+            data: vec![0; stride * height],
+            width,
+            height,
+            stride,
+        }
+    }
+
+    /// Parse a grid of characters, setting the cells that are `set`.
+    pub fn parse(input: &str, set: char) -> Self {
+        let width = input.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut board = Self::new(width, input.lines().count());
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                board.set(pos(x, y), c == set);
+            }
+        }
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn idx(&self, p: Pos) -> Option<(usize, u64)> {
+        if p.x < self.width && p.y < self.height {
+            Some((p.y * self.stride + p.x / 64, 1 << (p.x % 64)))
+        } else {
+            None
+        }
+    }
+
+    /// Whether a cell is set, which is never the case outside of the board.
+    pub fn get(&self, p: Pos) -> bool {
+        self.idx(p).is_some_and(|(idx, bit)| self.data[idx] & bit != 0)
+    }
+
+    pub fn set(&mut self, p: Pos, value: bool) {
+        let (idx, bit) = self.idx(p).expect("index out of bounds");
+        if value {
+            self.data[idx] |= bit;
+        } else {
+            self.data[idx] &= !bit;
+        }
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|&word| word == 0)
+    }
+
+    fn rows(&self) -> std::slice::ChunksExact<'_, u64> {
+        self.data.chunks_exact(self.stride.max(1))
+    }
+
+    /// The set cells, row by row from the top left.
+    pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().flat_map(move |(idx, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word.wrapping_sub(1);
+                    (bit < 64).then(|| pos(idx * 64 + bit, y))
+                })
+            })
+        })
+    }
+
+    /// Top left and bottom right corner of the set cells, or `None` if there are none.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut rows = self.rows().enumerate().filter(|(_, row)| row.iter().any(|&w| w != 0));
+        let (min_y, _) = rows.next()?;
+        let max_y = rows.next_back().map_or(min_y, |(y, _)| y);
+        let (mut min_x, mut max_x) = (usize::MAX, 0);
+        for row in self.rows() {
+            for (idx, &word) in row.iter().enumerate().filter(|(_, &w)| w != 0) {
+                min_x = min_x.min(idx * 64 + word.trailing_zeros() as usize);
+                max_x = max_x.max(idx * 64 + 63 - word.leading_zeros() as usize);
+            }
+        }
+        Some((pos(min_x, min_y), pos(max_x, max_y)))
+    }
+
+    /// Move every cell one step in a direction, dropping the ones that leave the board.
+    pub fn shift(&self, dir: Dir8) -> Self {
+        let offset = dir.offset();
+        let mut res = Self::new(self.width, self.height);
+        let stride = self.stride;
+        for y in 0..self.height {
+            let Some(src) = y.checked_add_signed(-offset.y).filter(|&src| src < self.height)
+            else {
+                continue;
+            };
+            let from = &self.data[src * stride..(src + 1) * stride];
+            let to = &mut res.data[y * stride..(y + 1) * stride];
+            match offset.x {
+                1 => {
+                    let mut carry = 0;
+                    for (to, &word) in to.iter_mut().zip(from) {
+                        *to = word << 1 | carry;
+                        carry = word >> 63;
+                    }
+                }
+                -1 => {
+                    let mut carry = 0;
+                    for (to, &word) in to.iter_mut().zip(from).rev() {
+                        *to = word >> 1 | carry;
+                        carry = word << 63;
+                    }
+                }
+                _ => to.copy_from_slice(from),
+            }
+        }
+        res.clear_padding();
+        res
+    }
+
+    /// Clear the bits beyond the width of the board.
+    fn clear_padding(&mut self) {
+        if self.width.is_multiple_of(64) {
+            return;
+        }
+        let mask = (1 << (self.width % 64)) - 1;
+        for row in self.data.chunks_exact_mut(self.stride) {
+            row[self.stride - 1] &= mask;
+        }
+    }
+}
+
+impl Display for Bitboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                f.write_char(if self.get(pos(x, y)) { '#' } else { '.' })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        for word in self.data.iter_mut() {
+            *word = !*word;
+        }
+        self.clear_padding();
+        self
+    }
+}
+
+/// Cell-wise boolean operations on boards of the same size.
+macro_rules! bit_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl $op_assign<&Self> for Bitboard {
+            fn $fn_assign(&mut self, rhs: &Self) {
+                assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+                for (l, r) in self.data.iter_mut().zip(rhs.data.iter()) {
+                    l.$fn_assign(*r);
+                }
+            }
+        }
+
+        impl $op<&Self> for Bitboard {
+            type Output = Self;
+
+            fn $fn(mut self, rhs: &Self) -> Self::Output {
+                self.$fn_assign(rhs);
+                self
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifts() {
+        // Wide enough for two words per row, with the cells around the boundary between them.
+        let mut board = Bitboard::new(70, 3);
+        board.set(pos(63, 1), true);
+        board.set(pos(69, 0), true);
+        assert_eq!(board.count_ones(), 2);
+        assert_eq!(board.bounds(), Some((pos(63, 0), pos(69, 1))));
+
+        let moved = board.shift(Dir8::SE);
+        assert_eq!(moved.iter().collect::<Vec<_>>(), [pos(64, 2)]);
+        let moved = board.shift(Dir8::NW);
+        assert_eq!(moved.iter().collect::<Vec<_>>(), [pos(62, 0)]);
+        assert_eq!(board.shift(Dir8::EA).shift(Dir8::WE).count_ones(), 1);
+
+        let full = !Bitboard::new(70, 3);
+        assert_eq!(full.count_ones(), 210);
+        assert_eq!((full.clone() ^ &board).count_ones(), 208);
+        assert_eq!((full & &board).iter().collect::<Vec<_>>(), [pos(69, 0), pos(63, 1)]);
+        assert_eq!(Bitboard::new(70, 3).bounds(), None);
+
+        let board = Bitboard::parse("..#\n#..\n", '#');
+        assert_eq!(board.shift(Dir8::SO).to_string(), "...\n..#\n");
+        assert_eq!((board.shift(Dir8::EA) | &board).to_string(), "..#\n##.\n");
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     fmt::{Display, Write},
-    ops::{Add, AddAssign, Index, IndexMut},
+    ops::{Add, AddAssign, Index, IndexMut, Sub},
 };
 
 use crate::util::graph::{Graph, GraphImpl};
@@ -24,7 +24,7 @@ impl Pos {
     }
 
     /// Generates a sequence of positions in a line from self to other (inclusive).
-    pub fn line(self, other: &Self) -> Option<Line> {
+    pub fn line(self, other: &Self) -> Option<Line<'_>> {
         if self.x != other.x && self.y != other.y {
             None
         } else {
@@ -105,6 +105,163 @@ impl Add<Pos> for Pos {
     }
 }
 
+/// A position that can also be left of or above the origin,
+/// for simulations that spread in every direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct IPos {
+    pub x: isize,
+    pub y: isize,
+}
+
+pub fn ipos(x: isize, y: isize) -> IPos {
+    IPos { x, y }
+}
+
+impl IPos {
+    pub const ZERO: Self = IPos { x: 0, y: 0 };
+
+    // Manhattan distance to other position.
+    pub fn dist(&self, other: &IPos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn step(self, dir: Dir4) -> Self {
+        self + dir.offset()
+    }
+
+    pub fn step_dir8(self, dir: Dir8) -> Self {
+        self + dir.offset()
+    }
+
+    pub fn swap_xy(self) -> Self {
+        IPos {
+            x: self.y,
+            y: self.x,
+        }
+    }
+}
+
+impl AddAssign for IPos {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Add<IPos> for IPos {
+    type Output = IPos;
+
+    fn add(self, rhs: IPos) -> Self::Output {
+        IPos {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub<IPos> for IPos {
+    type Output = IPos;
+
+    fn sub(self, rhs: IPos) -> Self::Output {
+        IPos {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl From<Pos> for IPos {
+    fn from(p: Pos) -> Self {
+        ipos(p.x as isize, p.y as isize)
+    }
+}
+
+impl TryFrom<IPos> for Pos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: IPos) -> Result<Self, Self::Error> {
+        Ok(pos(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+/// Which cells are the neighbors of a cell in a grid graph:
+/// the cells at some offsets from it, optionally wrapping around the edges like on a torus.
+/// The offsets are [`IPos`] for 2D grids, and [`grid3d::IPos`](super::grid3d::IPos) for 3D ones.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Neighborhood<P: 'static = IPos> {
+    offsets: &'static [P],
+    wrap: bool,
+}
+
+impl<P> Neighborhood<P> {
+    /// The cells at the given offsets, like `&[ipos(0, 0), ipos(1, 0), ipos(0, 1)]`
+    /// to also wait in place.
+    pub const fn stencil(offsets: &'static [P]) -> Self {
+        Self { offsets, wrap: false }
+    }
+
+    /// The same neighbors, wrapping around the edges of the grid.
+    /// Grids without edges ignore this, and a grid without cells has nothing to wrap to.
+    pub const fn wrapping(self) -> Self {
+        Self { offsets: self.offsets, wrap: true }
+    }
+
+    /// Offsets from a cell to its neighbors.
+    pub fn offsets(self) -> &'static [P] {
+        self.offsets
+    }
+
+    pub fn wraps(self) -> bool {
+        self.wrap
+    }
+
+    /// The coordinate `c` of a neighbor along an axis of `len` cells from the origin,
+    /// if it has one there.
+    pub(super) fn coord(self, c: isize, len: usize) -> Option<isize> {
+        let len = len as isize;
+        if self.wrap && len > 0 {
+            Some(c.rem_euclid(len))
+        } else {
+            (0..len).contains(&c).then_some(c)
+        }
+    }
+}
+
+impl Neighborhood {
+    /// The orthogonally adjacent cells.
+    pub const FOUR: Self = Self::stencil(&[
+        IPos { x: 0, y: -1 },
+        IPos { x: 1, y: 0 },
+        IPos { x: 0, y: 1 },
+        IPos { x: -1, y: 0 },
+    ]);
+
+    /// The orthogonally and diagonally adjacent cells.
+    pub const EIGHT: Self = Self::stencil(&[
+        IPos { x: 0, y: -1 },
+        IPos { x: 1, y: -1 },
+        IPos { x: 1, y: 0 },
+        IPos { x: 1, y: 1 },
+        IPos { x: 0, y: 1 },
+        IPos { x: -1, y: 1 },
+        IPos { x: -1, y: 0 },
+        IPos { x: -1, y: -1 },
+    ]);
+
+    /// The neighbor at `offset` from `center` in a grid of `width` by `height` cells
+    /// from the origin, if it has one there.
+    fn neighbor(self, center: IPos, offset: IPos, width: usize, height: usize) -> Option<IPos> {
+        let p = center + offset;
+        Some(ipos(self.coord(p.x, width)?, self.coord(p.y, height)?))
+    }
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Self::FOUR
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Rot {
     L,
@@ -183,6 +340,16 @@ impl Dir4 {
             _ => None,
         }
     }
+
+    /// Offset of a step in this direction, with y growing to the south.
+    pub fn offset(self) -> IPos {
+        match self {
+            Dir4::N => ipos(0, -1),
+            Dir4::E => ipos(1, 0),
+            Dir4::S => ipos(0, 1),
+            Dir4::W => ipos(-1, 0),
+        }
+    }
 }
 
 impl std::fmt::Display for Dir4 {
@@ -260,6 +427,20 @@ impl Dir8 {
             Dir8::NW => 7,
         }
     }
+
+    /// Offset of a step in this direction, with y growing to the south.
+    pub fn offset(self) -> IPos {
+        match self {
+            Dir8::NO => ipos(0, -1),
+            Dir8::NE => ipos(1, -1),
+            Dir8::EA => ipos(1, 0),
+            Dir8::SE => ipos(1, 1),
+            Dir8::SO => ipos(0, 1),
+            Dir8::SW => ipos(-1, 1),
+            Dir8::WE => ipos(-1, 0),
+            Dir8::NW => ipos(-1, -1),
+        }
+    }
 }
 
 impl std::fmt::Display for Dir8 {
@@ -321,6 +502,7 @@ pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
     height: usize,
+    neighborhood: Neighborhood,
 }
 
 impl<T: Clone> Grid<T> {
@@ -330,11 +512,13 @@ impl<T: Clone> Grid<T> {
             data,
             width,
             height,
+            neighborhood: Neighborhood::FOUR,
         }
     }
 
     pub fn pad(&self, amount: usize, value: T) -> Self {
         let mut result = Self::new_filled(self.width + amount * 2, self.height + amount * 2, value);
+        result.neighborhood = self.neighborhood;
         for x in 0..self.width {
             for y in 0..self.height {
                 result[pos(x + amount, y + amount)] = self[pos(x, y)].clone();
@@ -345,6 +529,7 @@ impl<T: Clone> Grid<T> {
 
     pub fn crop(&self, amount: usize, dummy: T) -> Self {
         let mut result = Self::new_filled(self.width - amount * 2, self.height - amount * 2, dummy);
+        result.neighborhood = self.neighborhood;
         for x in 0..result.width {
             for y in 0..result.height {
                 result[pos(x, y)] = self[pos(x + amount, y + amount)].clone();
@@ -372,6 +557,26 @@ impl<T: Clone> Grid<T> {
         }
         grid
     }
+
+    /// Mirror the grid along its diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.view().transpose().to_grid()
+    }
+
+    /// Rotate the grid a quarter turn.
+    pub fn rotate(&self, r: Rot) -> Self {
+        self.view().rotate(r).to_grid()
+    }
+
+    /// Mirror the grid horizontally, so that the columns are reversed.
+    pub fn flip_x(&self) -> Self {
+        self.view().flip_x().to_grid()
+    }
+
+    /// Mirror the grid vertically, so that the rows are reversed.
+    pub fn flip_y(&self) -> Self {
+        self.view().flip_y().to_grid()
+    }
 }
 
 impl<T: Default + Clone> Grid<T> {
@@ -379,6 +584,7 @@ impl<T: Default + Clone> Grid<T> {
         let width = end.x - start.x;
         let height = end.y - start.y;
         let mut result = Self::new_filled(width, height, T::default());
+        result.neighborhood = self.neighborhood;
         for x in 0..width {
             for y in 0..height {
                 result[pos(x, y)] = self[pos(x + start.x, y + start.y)].clone();
@@ -407,9 +613,16 @@ impl<T> Grid<T> {
             data,
             width,
             height,
+            neighborhood: Neighborhood::FOUR,
         }
     }
 
+    /// Set which cells are the neighbors of a cell when used as a [`Graph`].
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
     pub fn get(&self, p: Pos) -> Option<&T> {
         if p.x < self.width && p.y < self.height {
             Some(&self.data[p.x + p.y * self.width])
@@ -438,6 +651,38 @@ impl<T> Grid<T> {
         p.x < self.width && p.y < self.height
     }
 
+    /// The cells of a row from left to right, or from right to left with `.rev()`.
+    pub fn row(&self, y: usize) -> std::slice::Iter<'_, T> {
+        self.data[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// The cells of a column from top to bottom, or from bottom to top with `.rev()`.
+    pub fn column(&self, x: usize) -> std::iter::StepBy<std::slice::Iter<'_, T>> {
+        assert!(x < self.width, "column out of bounds");
+        self.data[x..].iter().step_by(self.width)
+    }
+
+    /// The rows from top to bottom, of which there are none if the grid is 0 wide.
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.data.chunks_exact(self.width.max(1))
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = std::iter::StepBy<std::slice::Iter<'_, T>>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// A view of the grid to transform without copying it.
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            transposed: false,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
     pub fn find_pos<P>(&self, p: P) -> Option<Pos>
     where
         P: Copy + Fn(&T) -> bool,
@@ -445,7 +690,7 @@ impl<T> Grid<T> {
         for x in 0..self.width {
             for y in 0..self.height {
                 let pos = Pos { x, y };
-                if self.get(pos).map_or(false, p) {
+                if self.get(pos).is_some_and(p) {
                     return Some(pos);
                 }
             }
@@ -472,10 +717,11 @@ impl<T> GraphImpl<T> for Grid<T> {
 
     fn neighbors(&self, node: Pos) -> Self::Neighbors {
         Neighbors {
-            center: node,
+            center: node.into(),
             width: self.width,
             height: self.height,
-            state: 0,
+            neighborhood: self.neighborhood,
+            offsets: self.neighborhood.offsets().iter(),
         }
     }
     type Neighbors = Neighbors;
@@ -502,6 +748,7 @@ impl<T> GraphImpl<T> for Grid<T> {
             data,
             width: self.width,
             height: self.height,
+            neighborhood: self.neighborhood,
         }
     }
     type Map<U> = Grid<U>;
@@ -533,46 +780,388 @@ impl<T> IndexMut<Pos> for Grid<T> {
     }
 }
 
+/// The neighbors of a cell in a [`Grid`].
 pub struct Neighbors {
-    center: Pos,
+    center: IPos,
     width: usize,
     height: usize,
-    state: u8,
+    neighborhood: Neighborhood,
+    offsets: std::slice::Iter<'static, IPos>,
 }
 
 impl Iterator for Neighbors {
     type Item = Pos;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut pos = self.center;
-        let f = |x: &mut usize, length: usize, inc: bool| -> bool {
-            if inc {
-                if *x < length - 1 {
-                    *x += 1;
-                    true
-                } else {
-                    false
-                }
-            } else if *x > 0 {
-                *x -= 1;
-                true
+        for &offset in self.offsets.by_ref() {
+            let neighbor = self
+                .neighborhood
+                .neighbor(self.center, offset, self.width, self.height);
+            if let Some(p) = neighbor {
+                return Some(pos(p.x as usize, p.y as usize));
+            }
+        }
+        None
+    }
+}
+
+/// A transformed view of a [`Grid`], which maps its positions to the grid when reading cells.
+/// This allows algorithms that scan in every direction to be written once,
+/// by running them on the view rotated in each direction.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    /// Whether the x and y of the grid are swapped, after flipping.
+    transposed: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        if self.transposed {
+            self.grid.height
+        } else {
+            self.grid.width
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.transposed {
+            self.grid.width
+        } else {
+            self.grid.height
+        }
+    }
+
+    /// The position in the grid that a position in the view shows.
+    pub fn source(&self, p: Pos) -> Pos {
+        let x = if self.flip_x { self.width() - 1 - p.x } else { p.x };
+        let y = if self.flip_y { self.height() - 1 - p.y } else { p.y };
+        if self.transposed {
+            pos(y, x)
+        } else {
+            pos(x, y)
+        }
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&'a T> {
+        if p.x < self.width() && p.y < self.height() {
+            self.grid.get(self.source(p))
+        } else {
+            None
+        }
+    }
+
+    pub fn transpose(self) -> Self {
+        GridView {
+            transposed: !self.transposed,
+            flip_x: self.flip_y,
+            flip_y: self.flip_x,
+            ..self
+        }
+    }
+
+    pub fn rotate(self, r: Rot) -> Self {
+        match r {
+            Rot::L => self.transpose().flip_y(),
+            Rot::R => self.transpose().flip_x(),
+        }
+    }
+
+    pub fn flip_x(self) -> Self {
+        GridView {
+            flip_x: !self.flip_x,
+            ..self
+        }
+    }
+
+    pub fn flip_y(self) -> Self {
+        GridView {
+            flip_y: !self.flip_y,
+            ..self
+        }
+    }
+
+    /// The cells of a row from left to right, or from right to left with `.rev()`.
+    pub fn row(self, y: usize) -> impl DoubleEndedIterator<Item = &'a T> {
+        (0..self.width()).map(move |x| &self.grid[self.source(pos(x, y))])
+    }
+
+    /// The cells of a column from top to bottom, or from bottom to top with `.rev()`.
+    pub fn column(self, x: usize) -> impl DoubleEndedIterator<Item = &'a T> {
+        (0..self.height()).map(move |y| &self.grid[self.source(pos(x, y))])
+    }
+
+    pub fn rows(self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &'a T>> {
+        (0..self.height()).map(move |y| self.row(y))
+    }
+
+    pub fn columns(
+        self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &'a T>> {
+        (0..self.width()).map(move |x| self.column(x))
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the transformed cells into a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            data: self.rows().flatten().cloned().collect(),
+            width: self.width(),
+            height: self.height(),
+            neighborhood: self.grid.neighborhood,
+        }
+    }
+}
+
+impl<T: Display> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Pos> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+/// A grid that grows in any direction to fit the cells written to it,
+/// for simulations whose extent isn't known in advance.
+/// Cells outside of the grid read as the default value it was created with.
+///
+/// Writing with [`GrowGrid::set`] or [`GrowGrid::get_mut`] grows the grid,
+/// while writing by index, like in any [`Graph`], keeps its structure
+/// and panics outside of it. Reading by index gives the default value there.
+#[derive(Clone)]
+pub struct GrowGrid<T> {
+    data: Vec<T>,
+    /// Position of the top left cell.
+    origin: IPos,
+    width: usize,
+    height: usize,
+    default: T,
+    neighborhood: Neighborhood,
+}
+
+impl<T: Clone> GrowGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            data: Vec::new(),
+            origin: IPos::ZERO,
+            width: 0,
+            height: 0,
+            default,
+            neighborhood: Neighborhood::FOUR,
+        }
+    }
+
+    /// Put a grid with its top left cell at the origin.
+    pub fn from_grid(grid: Grid<T>, default: T) -> Self {
+        Self {
+            data: grid.data,
+            origin: IPos::ZERO,
+            width: grid.width,
+            height: grid.height,
+            default,
+            neighborhood: grid.neighborhood,
+        }
+    }
+
+    pub fn get_mut(&mut self, p: IPos) -> &mut T {
+        self.reserve(p);
+        let idx = self.idx(p).unwrap();
+        &mut self.data[idx]
+    }
+
+    pub fn set(&mut self, p: IPos, value: T) {
+        *self.get_mut(p) = value;
+    }
+
+    /// Grow the grid to contain `p`.
+    /// It grows by at least its own size in that direction,
+    /// so that growing one cell at a time takes amortized constant time.
+    pub fn reserve(&mut self, p: IPos) {
+        if self.contains(p) {
+            return;
+        }
+        if self.data.is_empty() {
+            self.origin = p;
+            self.width = 1;
+            self.height = 1;
+            self.data = vec![self.default.clone()];
+            return;
+        }
+        let grow = |start: isize, size: usize, x: isize| {
+            let size = size as isize;
+            if x < start {
+                (x.min(start - size), start + size)
+            } else if x >= start + size {
+                (start, x.max(start + 2 * size - 1) + 1)
             } else {
-                false
+                (start, start + size)
             }
         };
-        self.state += 1;
-        let b = match self.state {
-            1 => f(&mut pos.x, self.width, true),
-            2 => f(&mut pos.y, self.height, true),
-            3 => f(&mut pos.x, self.width, false),
-            4 => f(&mut pos.y, self.height, false),
-            _ => return None,
-        };
-        if b {
-            Some(pos)
-        } else {
-            self.next()
+        let (x0, x1) = grow(self.origin.x, self.width, p.x);
+        let (y0, y1) = grow(self.origin.y, self.height, p.y);
+        let width = (x1 - x0) as usize;
+        let height = (y1 - y0) as usize;
+        let mut data = vec![self.default.clone(); width * height];
+        let dx = (self.origin.x - x0) as usize;
+        let dy = (self.origin.y - y0) as usize;
+        for (y, row) in self.data.chunks_exact(self.width).enumerate() {
+            let start = dx + (y + dy) * width;
+            data[start..start + self.width].clone_from_slice(row);
         }
+        self.data = data;
+        self.origin = ipos(x0, y0);
+        self.width = width;
+        self.height = height;
+    }
+}
+
+impl<T> GrowGrid<T> {
+    /// Set which cells are the neighbors of a cell when used as a [`Graph`].
+    /// A [wrapping](Neighborhood::wrapping) one wraps around the edges
+    /// the grid has grown to so far.
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    fn idx(&self, p: IPos) -> Option<usize> {
+        let x = usize::try_from(p.x - self.origin.x).ok()?;
+        let y = usize::try_from(p.y - self.origin.y).ok()?;
+        (x < self.width && y < self.height).then_some(x + y * self.width)
+    }
+
+    /// The value of a cell, or the default value outside of the grid.
+    pub fn get(&self, p: IPos) -> &T {
+        self.idx(p).map_or(&self.default, |idx| &self.data[idx])
+    }
+
+    pub fn contains(&self, p: IPos) -> bool {
+        self.idx(p).is_some()
+    }
+
+    /// Position of the top left cell.
+    pub fn min(&self) -> IPos {
+        self.origin
+    }
+
+    /// Position of the bottom right cell.
+    /// The grid may extend beyond the cells that were written, as it grows in steps.
+    pub fn max(&self) -> IPos {
+        self.origin + ipos(self.width as isize - 1, self.height as isize - 1)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T> Graph<T> for GrowGrid<T> {}
+impl<T> GraphImpl<T> for GrowGrid<T> {
+    type Node = IPos;
+
+    fn neighbors(&self, node: IPos) -> Self::Neighbors {
+        GrowNeighbors {
+            neighbors: Neighbors {
+                center: node - self.origin,
+                width: self.width,
+                height: self.height,
+                neighborhood: self.neighborhood,
+                offsets: self.neighborhood.offsets().iter(),
+            },
+            origin: self.origin,
+        }
+    }
+    type Neighbors = GrowNeighbors;
+
+    fn nodes(&self) -> Self::AllNodes {
+        let mut res = Vec::with_capacity(self.data.len());
+        for y in 0..self.height as isize {
+            for x in 0..self.width as isize {
+                res.push(self.origin + ipos(x, y));
+            }
+        }
+        res.into_iter()
+    }
+    type AllNodes = std::vec::IntoIter<IPos>;
+
+    fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Self::Map<U> {
+        GrowGrid {
+            data: self.data.iter().map(&mut f).collect(),
+            origin: self.origin,
+            width: self.width,
+            height: self.height,
+            default: f(&self.default),
+            neighborhood: self.neighborhood,
+        }
+    }
+    type Map<U> = GrowGrid<U>;
+}
+
+impl<T: Display> Display for GrowGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.data.chunks_exact(self.width.max(1)) {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<IPos> for GrowGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: IPos) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> IndexMut<IPos> for GrowGrid<T> {
+    fn index_mut(&mut self, index: IPos) -> &mut Self::Output {
+        let idx = self.idx(index).expect("index out of bounds");
+        &mut self.data[idx]
+    }
+}
+
+/// The neighbors of a cell in a [`GrowGrid`] that are inside of it.
+pub struct GrowNeighbors {
+    /// Neighbors relative to the origin of the grid.
+    neighbors: Neighbors,
+    origin: IPos,
+}
+
+impl Iterator for GrowNeighbors {
+    type Item = IPos;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(IPos::from(self.neighbors.next()?) + self.origin)
     }
 }
 
@@ -645,3 +1234,104 @@ impl Display for BitGrid {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{
+        pathfinding::{a_star, bfs, dijkstra},
+        queue::RadixHeap,
+    };
+
+    #[test]
+    fn grow_grid() {
+        let start = ipos(0, 0);
+        assert_eq!(start.step(Dir4::N).step_dir8(Dir8::SW), ipos(-1, 0));
+        assert_eq!(Pos::try_from(ipos(-1, 0)).ok(), None);
+
+        let mut grid = GrowGrid::new('.');
+        grid.set(start, '#');
+        grid.set(ipos(-2, -1), '#');
+        grid.set(ipos(1, 2), '#');
+        assert_eq!(grid[ipos(-2, -1)], '#');
+        assert_eq!(grid[ipos(0, 1)], '.');
+        assert_eq!(grid[ipos(-100, 100)], '.');
+        assert!(grid.min().x <= -2 && grid.min().y <= -1);
+        assert!(grid.max().x >= 1 && grid.max().y >= 2);
+        assert_eq!(grid.to_string().matches('#').count(), 3);
+
+        let target = ipos(1, 2);
+        let distance = bfs(&grid, start, |_, n| grid[n] == '.' || n == target, |_, n| n == target);
+        assert_eq!(distance, Some(start.dist(&target)));
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let grid = Grid::new_filled(5, 4, 1);
+        let (start, end) = (pos(0, 0), pos(4, 3));
+        let steps = |grid: &Grid<usize>| bfs(grid, start, |_, _| true, |_, p| p == end);
+        assert_eq!(steps(&grid), Some(7));
+        assert_eq!(steps(&grid.clone().with_neighborhood(Neighborhood::EIGHT)), Some(4));
+        let wrapping = Neighborhood::FOUR.wrapping();
+        assert_eq!(steps(&grid.clone().with_neighborhood(wrapping)), Some(2));
+        let wrapping = Neighborhood::EIGHT.wrapping();
+        assert_eq!(steps(&grid.clone().with_neighborhood(wrapping)), Some(1));
+        let empty = Grid::new_filled(0, 0, 1).with_neighborhood(wrapping);
+        assert_eq!(empty.neighbors(pos(0, 0)).count(), 0);
+        let empty = GrowGrid::new(0).with_neighborhood(wrapping);
+        assert_eq!(empty.neighbors(ipos(0, 0)).count(), 0);
+
+        const KNIGHT: [IPos; 2] = [IPos { x: 2, y: 1 }, IPos { x: 1, y: 2 }];
+        let knight = Neighborhood::stencil(&KNIGHT);
+        let wrapping = grid.clone().with_neighborhood(knight.wrapping());
+        assert_eq!(wrapping.neighbors(pos(3, 2)).count(), 2);
+        let grid = grid.with_neighborhood(knight);
+        assert_eq!(grid.neighbors(pos(3, 2)).count(), 0);
+        let cost = |_, n| Some(grid[n] + n.y);
+        let end = pos(3, 3);
+        let is_end = |_, p| p == end;
+        assert_eq!(dijkstra::<_, _, RadixHeap<_>>(&grid, cost, is_end, start), Some(6));
+        let heuristic = |p: Pos| p.dist(&end) / 3;
+        assert_eq!(a_star::<_, _, RadixHeap<_>>(&grid, cost, heuristic, is_end, start), Some(6));
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::parse("abc\ndef\n", |_, c| c);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate(Rot::R).to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate(Rot::L).to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_x().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_y().to_string(), "def\nabc\n");
+
+        assert_eq!(grid.row(1).rev().collect::<String>(), "fed");
+        assert_eq!(grid.column(1).rev().collect::<String>(), "eb");
+        assert_eq!(grid.columns().map(|mut c| c.next().unwrap()).collect::<String>(), "abc");
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+
+        let view = grid.view().rotate(Rot::R);
+        assert_eq!((view.width(), view.height()), (2, 3));
+        assert_eq!(view[pos(1, 2)], 'c');
+        assert_eq!(view.source(pos(1, 2)), pos(2, 0));
+        assert_eq!(view.row(0).rev().collect::<String>(), "ad");
+        assert_eq!(view.column(0).collect::<String>(), "def");
+        let turned = view.rotate(Rot::R).rotate(Rot::R).flip_x().flip_y().rotate(Rot::L);
+        assert_eq!(turned.to_string(), grid.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "column out of bounds")]
+    fn column_out_of_bounds() {
+        // The column would otherwise start in the next row.
+        let grid = Grid::parse("abc\ndef\n", |_, c| c);
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn empty_rows() {
+        let grid = Grid::new_filled(0, 3, 'a');
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+    }
+}
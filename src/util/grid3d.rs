@@ -1,6 +1,10 @@
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut};
 
 use crate::util::graph::{Graph, GraphImpl};
+use crate::util::grid;
+
+/// Which cells are the neighbors of a cell in a 3D grid graph, see [`grid::Neighborhood`].
+pub type Neighborhood = grid::Neighborhood<IPos>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Pos {
@@ -22,12 +26,57 @@ impl Pos {
     }
 }
 
+/// A position that can be outside of a grid, or an offset between positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IPos {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+pub const fn ipos(x: isize, y: isize, z: isize) -> IPos {
+    IPos { x, y, z }
+}
+
+impl Add for IPos {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        ipos(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl From<Pos> for IPos {
+    fn from(p: Pos) -> Self {
+        ipos(p.x as isize, p.y as isize, p.z as isize)
+    }
+}
+
+impl Neighborhood {
+    /// The cells sharing a face.
+    pub const SIX: Self = Self::stencil(&[
+        ipos(1, 0, 0),
+        ipos(-1, 0, 0),
+        ipos(0, 1, 0),
+        ipos(0, -1, 0),
+        ipos(0, 0, 1),
+        ipos(0, 0, -1),
+    ]);
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Self::SIX
+    }
+}
+
 #[derive(Clone)]
 pub struct Grid<T> {
     data: Vec<T>,
     width: usize,
     height: usize,
     depth: usize,
+    neighborhood: Neighborhood,
 }
 
 impl<T: Clone> Grid<T> {
@@ -38,11 +87,18 @@ impl<T: Clone> Grid<T> {
             width,
             height,
             depth,
+            neighborhood: Neighborhood::SIX,
         }
     }
 }
 
 impl<T> Grid<T> {
+    /// Set which cells are the neighbors of a cell when used as a [`Graph`].
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
     pub fn get(&self, p: Pos) -> Option<&T> {
         if p.x < self.width && p.y < self.height && p.z < self.depth {
             Some(&self.data[p.x + self.width * (p.y + self.height * p.z)])
@@ -83,7 +139,7 @@ impl<T> Grid<T> {
             for y in 0..self.height {
                 for x in 0..self.width {
                     let pos = Pos { x, y, z };
-                    if self.get(pos).map_or(false, pred) {
+                    if self.get(pos).is_some_and(pred) {
                         return Some(pos);
                     }
                 }
@@ -114,11 +170,10 @@ impl<T> GraphImpl<T> for Grid<T> {
     type Neighbors = Neighbors;
     fn neighbors(&self, node: Pos) -> Self::Neighbors {
         Neighbors {
-            center: node,
-            width: self.width,
-            height: self.height,
-            depth: self.depth,
-            state: 0,
+            center: node.into(),
+            size: [self.width, self.height, self.depth],
+            neighborhood: self.neighborhood,
+            offsets: self.neighborhood.offsets().iter(),
         }
     }
 
@@ -150,6 +205,7 @@ impl<T> GraphImpl<T> for Grid<T> {
             width: self.width,
             height: self.height,
             depth: self.depth,
+            neighborhood: self.neighborhood,
         }
     }
 }
@@ -168,38 +224,50 @@ impl<T> IndexMut<Pos> for Grid<T> {
     }
 }
 
+/// The neighbors of a cell in a [`Grid`].
 pub struct Neighbors {
-    center: Pos,
-    width: usize,
-    height: usize,
-    depth: usize,
-    state: u8,
+    center: IPos,
+    size: [usize; 3],
+    neighborhood: Neighborhood,
+    offsets: std::slice::Iter<'static, IPos>,
 }
 
 impl Iterator for Neighbors {
     type Item = Pos;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut pos = self.center;
-        #[rustfmt::skip]
-        let f = |x: &mut usize, length: usize, inc: bool| -> bool {
-            if inc { if *x < length - 1 { *x += 1; true } else { false } }
-            else if *x > 0 { *x -= 1; true } else { false }
-        };
-        let b = match self.state {
-            0 => f(&mut pos.x, self.width, true),
-            1 => f(&mut pos.x, self.width, false),
-            2 => f(&mut pos.y, self.height, true),
-            3 => f(&mut pos.y, self.height, false),
-            4 => f(&mut pos.z, self.depth, true),
-            5 => f(&mut pos.z, self.depth, false),
-            _ => return None,
-        };
-        self.state += 1;
-        if b {
-            Some(pos)
-        } else {
-            self.next()
+        let [width, height, depth] = self.size;
+        for &offset in self.offsets.by_ref() {
+            let p = self.center + offset;
+            let coord = |c, len| self.neighborhood.coord(c, len);
+            let neighbor = (coord(p.x, width), coord(p.y, height), coord(p.z, depth));
+            if let (Some(x), Some(y), Some(z)) = neighbor {
+                return Some(pos(x as usize, y as usize, z as usize));
+            }
         }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighborhoods() {
+        let grid = Grid::new_filled(3, 2, 4, ());
+        assert_eq!(grid.neighbors(pos(0, 0, 0)).count(), 3);
+        assert_eq!(grid.neighbors(pos(1, 1, 1)).count(), 5);
+
+        let grid = grid.with_neighborhood(Neighborhood::SIX.wrapping());
+        assert_eq!(grid.neighbors(pos(0, 0, 0)).count(), 6);
+        assert!(grid.neighbors(pos(0, 0, 0)).any(|p| p == pos(2, 0, 0)));
+        assert!(grid.neighbors(pos(0, 0, 0)).any(|p| p == pos(0, 0, 3)));
+
+        const UP: [IPos; 1] = [ipos(0, 0, 1)];
+        let grid = grid.with_neighborhood(Neighborhood::stencil(&UP));
+        assert_eq!(grid.neighbors(pos(0, 0, 3)).count(), 0);
+        let empty = Grid::new_filled(0, 0, 0, ()).with_neighborhood(Neighborhood::SIX.wrapping());
+        assert_eq!(empty.neighbors(pos(0, 0, 0)).count(), 0);
     }
 }
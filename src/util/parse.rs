@@ -0,0 +1,236 @@
+use std::fmt::Display;
+
+/// An error in the input, located by line and column (both starting from 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A cursor over an input that keeps track of the current line and column.
+///
+/// Every method that consumes input either succeeds
+/// or returns an error pointing at the offending position.
+/// Newlines may also be `\r\n`, which is treated like `\n` everywhere.
+#[derive(Clone)]
+pub struct Scanner<'a> {
+    rest: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { rest: input, line: 1, column: 1 }
+    }
+
+    pub fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError { line: self.line, column: self.column, message: message.into() })
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        if self.rest.starts_with("\r\n") {
+            return Some('\n');
+        }
+        self.rest.chars().next()
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// The remainder of the current line, excluding the newline.
+    pub fn rest_of_line(&self) -> &'a str {
+        let line = self.rest.split('\n').next().unwrap();
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    fn advance(&mut self, bytes: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(bytes);
+        for c in taken.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if c != '\r' {
+                self.column += 1;
+            }
+        }
+        self.rest = rest;
+        taken
+    }
+
+    /// Consume `s` if the input starts with it.
+    pub fn try_literal(&mut self, s: &str) -> bool {
+        let mut len = 0;
+        for c in s.chars() {
+            let rest = &self.rest[len..];
+            if c == '\n' && rest.starts_with("\r\n") {
+                len += 2;
+            } else if rest.starts_with(c) {
+                len += c.len_utf8();
+            } else {
+                return false;
+            }
+        }
+        self.advance(len);
+        true
+    }
+
+    pub fn literal(&mut self, s: &str) -> Result<(), ParseError> {
+        if self.try_literal(s) {
+            Ok(())
+        } else {
+            self.error(format!("expected `{}`", s.escape_debug()))
+        }
+    }
+
+    /// Consume a single character from `chars`.
+    pub fn one_of(&mut self, chars: &str) -> Result<char, ParseError> {
+        match self.peek() {
+            Some(c) if c != '\n' && chars.contains(c) => {
+                self.advance(c.len_utf8());
+                Ok(c)
+            }
+            _ => self.error(format!("expected one of `{}`", chars.escape_debug())),
+        }
+    }
+
+    /// Consume a non-empty sequence of characters matching `pred`.
+    pub fn many1(
+        &mut self,
+        what: &str,
+        pred: impl Fn(char) -> bool,
+    ) -> Result<&'a str, ParseError> {
+        let len = self.rest.find(|c| !pred(c)).unwrap_or(self.rest.len());
+        if len == 0 {
+            return self.error(format!("expected {what}"));
+        }
+        Ok(self.advance(len))
+    }
+
+    pub fn unsigned(&mut self) -> Result<u64, ParseError> {
+        let start = self.clone();
+        let digits = self.many1("unsigned integer", |c| c.is_ascii_digit())?;
+        digits
+            .parse()
+            .or_else(|_| start.error("integer out of range"))
+    }
+
+    pub fn signed(&mut self) -> Result<i64, ParseError> {
+        let start = self.clone();
+        let negative = self.try_literal("-");
+        let digits = self.many1("integer", |c| c.is_ascii_digit())?;
+        let value: i64 = digits
+            .parse()
+            .or_else(|_| start.error("integer out of range"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// A non-empty sequence of ASCII letters.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        self.many1("word", |c| c.is_ascii_alphabetic())
+    }
+
+    /// Consume a newline, or succeed at the end of the input.
+    pub fn eol(&mut self) -> Result<(), ParseError> {
+        if self.is_eof() || self.try_literal("\n") {
+            Ok(())
+        } else {
+            self.error("expected end of line")
+        }
+    }
+
+    /// Apply `line` to every remaining line of the input.
+    /// `line` should consume a line without its newline.
+    pub fn lines(
+        &mut self,
+        mut line: impl FnMut(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        while !self.is_eof() {
+            line(self)?;
+            self.eol()?;
+        }
+        Ok(())
+    }
+
+    /// Expect the end of the input, allowing trailing newlines.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        while self.try_literal("\n") {}
+        if self.is_eof() {
+            Ok(())
+        } else {
+            self.error("expected end of input")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let mut s = Scanner::new("move 1 from 2 to 3\nmove x");
+        let mut line = |s: &mut Scanner| -> Result<(), ParseError> {
+            s.literal("move ")?;
+            s.unsigned()?;
+            s.literal(" from ")?;
+            s.unsigned()?;
+            s.literal(" to ")?;
+            s.unsigned()?;
+            Ok(())
+        };
+        let err = s.lines(&mut line).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected unsigned integer"
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        let mut s = Scanner::new("-12,34,99999999999999999999");
+        assert_eq!(s.signed(), Ok(-12));
+        s.literal(",").unwrap();
+        assert_eq!(s.signed(), Ok(34));
+        s.literal(",").unwrap();
+        let err = s.unsigned().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (8, "integer out of range")
+        );
+    }
+
+    #[test]
+    fn end() {
+        assert!(Scanner::new("\n\n").end().is_ok());
+        assert_eq!(Scanner::new("\nx").end().unwrap_err().line, 2);
+        assert!(Scanner::new("a").eol().is_err());
+
+        let mut s = Scanner::new("ab\r\ncd\r\n\r\n");
+        assert_eq!(s.rest_of_line(), "ab");
+        s.word().unwrap();
+        assert_eq!(s.peek(), Some('\n'));
+        s.eol().unwrap();
+        s.literal("c").unwrap();
+        let err = s.literal("x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        s.literal("d\n").unwrap();
+        assert!(s.end().is_ok());
+        assert!(Scanner::new("a\r").end().is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! A small seeded random number generator,
+//! so that randomly generated inputs can be reproduced from their seed.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64, which is plenty for generating puzzle inputs and works with any seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range, with all numbers (nearly) equally likely.
+    pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        assert!(start <= end, "empty range");
+        let span = (end - start + 1) as u128;
+        T::from_i128(start + ((self.next_u64() as u128 * span) >> 64) as i128)
+    }
+
+    /// An index into a non-empty slice of the given length.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..=len - 1)
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.index(idx + 1));
+        }
+    }
+}
+
+/// Integers that [`Rng::range`] can produce.
+pub trait Uniform: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Self;
+}
+
+macro_rules! uniform {
+    ($($t:ty),+) => {
+        $(
+            impl Uniform for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(n: i128) -> Self {
+                    n as $t
+                }
+            }
+        )+
+    };
+}
+
+uniform!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(-3i32..=3)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| numbers(7).contains(&n)));
+
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.range(u64::MAX..=u64::MAX), u64::MAX);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
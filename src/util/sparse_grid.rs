@@ -0,0 +1,176 @@
+use std::{
+    fmt::{Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use fxhash::FxHashMap;
+
+use crate::util::{
+    graph::{Graph, GraphImpl},
+    grid::{ipos, IPos, Neighborhood},
+};
+
+/// A grid that only stores the cells that were set, for boards too large to allocate,
+/// like the sand of day 14 or the sensors of day 15.
+/// The other cells read as the default value it was created with.
+///
+/// As a [`Graph`], the nodes are the cells that were set,
+/// connected to the ones next to them in its [`Neighborhood`].
+/// Having no edges, it never wraps around.
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<IPos, T>,
+    default: T,
+    neighborhood: Neighborhood,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            default,
+            neighborhood: Neighborhood::FOUR,
+        }
+    }
+
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    /// The value of a cell, or the default value if it wasn't set.
+    pub fn get(&self, p: IPos) -> &T {
+        self.cells.get(&p).unwrap_or(&self.default)
+    }
+
+    /// Set a cell, returning its previous value if it was set.
+    pub fn set(&mut self, p: IPos, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    /// Unset a cell, so that it reads as the default value again.
+    pub fn remove(&mut self, p: IPos) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn contains(&self, p: IPos) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// Number of cells that are set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells that are set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (IPos, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    /// Top left and bottom right corner of the cells that are set,
+    /// or `None` if there are none.
+    pub fn bounds(&self) -> Option<(IPos, IPos)> {
+        let mut cells = self.cells.keys();
+        let &first = cells.next()?;
+        Some(cells.fold((first, first), |(min, max), p| {
+            (
+                ipos(min.x.min(p.x), min.y.min(p.y)),
+                ipos(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// A mutable reference to a cell, setting it to the default value if it wasn't set.
+    pub fn get_mut(&mut self, p: IPos) -> &mut T {
+        let default = &self.default;
+        self.cells.entry(p).or_insert_with(|| default.clone())
+    }
+}
+
+impl<T> Graph<T> for SparseGrid<T> {}
+impl<T> GraphImpl<T> for SparseGrid<T> {
+    type Node = IPos;
+
+    fn neighbors(&self, node: IPos) -> Self::Neighbors {
+        let offsets = self.neighborhood.offsets().iter();
+        let neighbors = offsets.map(|&offset| node + offset);
+        neighbors
+            .filter(|&p| self.contains(p))
+            .collect::<Vec<IPos>>()
+            .into_iter()
+    }
+    type Neighbors = std::vec::IntoIter<IPos>;
+
+    fn nodes(&self) -> Self::AllNodes {
+        self.cells.keys().copied().collect::<Vec<IPos>>().into_iter()
+    }
+    type AllNodes = std::vec::IntoIter<IPos>;
+
+    fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Self::Map<U> {
+        SparseGrid {
+            cells: self.cells.iter().map(|(&p, value)| (p, f(value))).collect(),
+            default: f(&self.default),
+            neighborhood: self.neighborhood,
+        }
+    }
+    type Map<U> = SparseGrid<U>;
+}
+
+/// Draws the bounding box of the cells that are set.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.get(ipos(x, y)).fmt(f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<IPos> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: IPos) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> IndexMut<IPos> for SparseGrid<T> {
+    fn index_mut(&mut self, index: IPos) -> &mut Self::Output {
+        self.cells.get_mut(&index).expect("cell is not set")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::pathfinding::bfs;
+
+    #[test]
+    fn sparse_grid() {
+        let mut grid = SparseGrid::new('.');
+        for p in [ipos(-1_000_000, 5), ipos(-999_999, 6), ipos(-999_998, 6), ipos(-999_998, 7)] {
+            grid.set(p, '#');
+        }
+        assert_eq!(grid[ipos(-999_999, 6)], '#');
+        assert_eq!(grid[ipos(0, 0)], '.');
+        assert_eq!(grid.bounds(), Some((ipos(-1_000_000, 5), ipos(-999_998, 7))));
+        assert_eq!(grid.to_string(), "#..\n.##\n..#\n");
+
+        let (start, end) = (ipos(-1_000_000, 5), ipos(-999_998, 7));
+        assert_eq!(bfs(&grid, start, |_, _| true, |_, p| p == end), None);
+        let grid = grid.with_neighborhood(Neighborhood::EIGHT);
+        assert_eq!(bfs(&grid, start, |_, _| true, |_, p| p == end), Some(2));
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Cell, CellAlignment, Color, Table};
+
+use aoc2022::profile::Profile;
+
+use crate::args::Args;
+use crate::output::parse_csv;
+use crate::snapshot;
+
+/// Modification times of the watched files, `None` for files that don't exist (yet).
+type Stamps = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Outcome of an implementation on a profile, read back from the CSV output of the runner.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Outcome {
+    answers: [Option<String>; 2],
+    correct: [Option<bool>; 2],
+    time: Option<Duration>,
+    error: Option<String>,
+}
+
+/// Outcomes by profile and implementation.
+type Results = BTreeMap<(String, String), Outcome>;
+
+/// Rebuild and re-run a single day whenever the sources or its inputs change,
+/// printing how the answers and times changed since the previous run.
+pub fn watch(args: &Args, profiles: &[&Profile], day: usize) -> ! {
+    // Look up the executable before the first build replaces it,
+    // after which it would refer to the deleted file.
+    let exe = std::env::current_exe().expect("couldn't find the runner executable");
+    let mut stamps = Stamps::new();
+    let mut previous = Results::new();
+    loop {
+        let current = modification_times(profiles, day);
+        let changed: Vec<&Path> = current
+            .iter()
+            .filter(|(path, time)| stamps.get(*path) != Some(time))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        if !changed.is_empty() {
+            if !stamps.is_empty() {
+                let changed: Vec<String> =
+                    changed.iter().map(|path| path.display().to_string()).collect();
+                println!("\nChanged: {}", changed.join(", "));
+            }
+            stamps = current;
+            if let Some(results) = rebuild_and_run(&exe, args, day) {
+                print_diff(args, day, &previous, &results);
+                previous = results;
+            }
+            println!("Watching for changes to day {day}...");
+        }
+        std::thread::sleep(args.interval);
+    }
+}
+
+/// Every file in `src/`, and the inputs and solutions of the day in each profile.
+fn modification_times(profiles: &[&Profile], day: usize) -> Stamps {
+    fn visit(dir: &Path, stamps: &mut Stamps) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                visit(&path, stamps);
+            } else {
+                let time = entry.metadata().and_then(|meta| meta.modified()).ok();
+                stamps.insert(path, time);
+            }
+        }
+    }
+    let mut stamps = Stamps::new();
+    visit(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut stamps);
+    for profile in profiles {
+        for path in [profile.input_path(day), profile.solution_path(day)] {
+            let time = std::fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            stamps.insert(path, time);
+        }
+    }
+    stamps
+}
+
+/// Arguments for running the day in a child process, forwarding the selection and timing options.
+fn run_args(args: &Args, day: usize) -> Vec<String> {
+    let mut run_args: Vec<String> = ["run", "--format", "csv", "--day"].map(String::from).into();
+    run_args.push(day.to_string());
+    for profile in args.profiles() {
+        run_args.extend([String::from("--profile"), profile.clone()]);
+    }
+    for o in &args.params {
+        run_args.extend([String::from("--param"), format!("day{}.{}={}", o.day, o.name, o.value)]);
+    }
+    match args.parts() {
+        [true, false] => run_args.extend(["--part", "1"].map(String::from)),
+        [false, true] => run_args.extend(["--part", "2"].map(String::from)),
+        _ => {}
+    }
+    if args.cross_check {
+        run_args.push(String::from("--cross-check"));
+    }
+    run_args.extend([String::from("--repeat"), args.repeat.runs.to_string()]);
+    let min_time = args.repeat.min_time.as_nanos();
+    run_args.extend([String::from("--min-time"), format!("{min_time}ns")]);
+    run_args
+}
+
+/// Rebuild the runner with cargo and run the day with the new binary.
+/// Prints the problem and returns `None` if either step fails.
+fn rebuild_and_run(exe: &Path, args: &Args, day: usize) -> Option<Results> {
+    let mut build = Command::new("cargo");
+    build.args(["build", "--quiet", "--bin", env!("CARGO_PKG_NAME")]);
+    build.current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    match build.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            println!("Build failed");
+            return None;
+        }
+        Err(err) => {
+            println!("Couldn't run cargo: {err}");
+            return None;
+        }
+    }
+    let output = Command::new(exe)
+        .args(run_args(args, day))
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| println!("Couldn't run day {day}: {err}"))
+        .ok()?;
+    // Exit status 1 only means that something failed or was wrong, which is part of the results.
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        println!("Running day {day} failed with {}", output.status);
+        return None;
+    }
+    parse_results(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|err| println!("Couldn't read the results of day {day}: {err}"))
+        .ok()
+}
+
+fn parse_results(csv: &str) -> Result<Results, String> {
+    let mut rows = parse_csv(csv).into_iter();
+    let header = rows.next().ok_or("no output")?;
+    let column = |name: &str| {
+        header.iter().position(|h| h == name).ok_or_else(|| format!("no column `{name}`"))
+    };
+    let (profile, implementation) = (column("profile")?, column("implementation")?);
+    let (part, answer, correct) = (column("part")?, column("answer")?, column("correct")?);
+    let (time, error) = (column("time_ns")?, column("error")?);
+
+    let mut results = Results::new();
+    for row in rows {
+        if row.len() != header.len() {
+            return Err(format!("expected {} fields, got {}", header.len(), row.len()));
+        }
+        let field = |idx: usize| row.get(idx).filter(|s| !s.is_empty());
+        let key = (row[profile].clone(), row[implementation].clone());
+        let outcome = results.entry(key).or_default();
+        let part = match row[part].as_str() {
+            "1" => 0,
+            "2" => 1,
+            part => return Err(format!("invalid part `{part}`")),
+        };
+        outcome.answers[part] = field(answer).cloned();
+        outcome.correct[part] = field(correct).map(|correct| correct == "true");
+        outcome.time = field(time).and_then(|ns| ns.parse().ok()).map(Duration::from_nanos);
+        outcome.error = field(error).cloned();
+    }
+    Ok(results)
+}
+
+/// Print the results of a run next to the changes from the previous run.
+fn print_diff(args: &Args, day: usize, previous: &Results, results: &Results) {
+    let parts = args.parts();
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED);
+    let mut header = vec!["Profile", "Implementation"];
+    header.extend((0..2).filter(|&part| parts[part]).map(|part| ["Part 1", "Part 2"][part]));
+    header.extend(["Time", "Change"]);
+    table.set_header(header);
+
+    for ((profile, implementation), outcome) in results {
+        let old = previous.get(&(profile.clone(), implementation.clone()));
+        let mut row = vec![Cell::new(profile), Cell::new(implementation)];
+        for part in (0..2).filter(|&part| parts[part]) {
+            row.push(answer_cell(old, outcome, part));
+        }
+        let right = |s: String| Cell::new(s).set_alignment(CellAlignment::Right);
+        match outcome.time {
+            Some(time) => {
+                row.push(right(format!("{time:.2?}")));
+                let old = old.and_then(|old| old.time);
+                row.push(match old.and_then(|old| Some((old, snapshot::change(old, time)?))) {
+                    Some((old, change)) => {
+                        let color = if change > args.threshold {
+                            Color::Red
+                        } else if change < -args.threshold {
+                            Color::Green
+                        } else {
+                            Color::Reset
+                        };
+                        right(format!("{change:+.1}% (was {old:.2?})")).fg(color)
+                    }
+                    None => right(String::from("?")),
+                });
+            }
+            None => row.extend([right(String::new()), right(String::new())]),
+        }
+        table.add_row(row);
+    }
+    println!("Day {day}\n{table}");
+}
+
+/// The answer of a part, highlighting changes from the previous answer and wrong answers.
+fn answer_cell(old: Option<&Outcome>, new: &Outcome, part: usize) -> Cell {
+    if let Some(error) = &new.error {
+        return Cell::new(error).fg(Color::Red);
+    }
+    let answer = new.answers[part].as_deref().unwrap_or_default();
+    let color = match new.correct[part] {
+        Some(true) => Color::Green,
+        Some(false) => Color::Red,
+        None => Color::Reset,
+    };
+    match old.map(|old| old.answers[part].as_deref().unwrap_or_default()) {
+        Some(old) if old != answer => Cell::new(format!("{old} -> {answer}")).fg(color),
+        _ => Cell::new(answer).fg(color),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results() {
+        let csv = "\
+profile,day,implementation,part,answer,time_ns,min_ns,mean_ns,std_dev_ns,runs,time_reliable,\
+correct,error
+github,16,optimized::day16,1,1651,1500,1500,1500,0,1,true,true,
+github,16,optimized::day16,2,\"17\n07\",1500,1500,1500,0,1,true,false,
+other,16,optimized::day16,1,,,,,,,,,missing input day16.txt
+";
+        let results = parse_results(csv).unwrap();
+        let key = |profile: &str| (profile.to_owned(), String::from("optimized::day16"));
+        let github = &results[&key("github")];
+        assert_eq!(github.answers, [Some(String::from("1651")), Some(String::from("17\n07"))]);
+        assert_eq!(github.correct, [Some(true), Some(false)]);
+        assert_eq!(github.time, Some(Duration::from_nanos(1500)));
+        let other = &results[&key("other")];
+        assert_eq!(other.error.as_deref(), Some("missing input day16.txt"));
+        assert_eq!(other.time, None);
+        assert!(parse_results("").is_err());
+    }
+
+    #[test]
+    fn forwarded_args() {
+        let argv = ["watch", "--day", "16", "--profile", "a", "--part", "2", "--repeat", "3"];
+        let params = ["--param", "day16.minutes_1=20"];
+        let args = Args::parse(argv.into_iter().chain(params).map(String::from)).unwrap();
+        let run_args = run_args(&args, 16).join(" ");
+        assert_eq!(
+            run_args,
+            "run --format csv --day 16 --profile a --param day16.minutes_1=20 --part 2 \
+             --repeat 3 --min-time 0ns"
+        );
+        assert!(Args::parse(run_args.split(' ').map(String::from)).is_ok());
+    }
+}
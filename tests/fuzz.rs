@@ -0,0 +1,24 @@
+// Every implementation should agree with the others of its day on generated inputs,
+// without panicking or getting stuck, which `fuzz` checks like it does on the command line.
+// This takes minutes, so it only runs with `cargo test --release --test fuzz -- --ignored`.
+
+use std::process::Command;
+
+/// The days to fuzz. The reference implementations of days 16 and 19 search exhaustively,
+/// and take longer than the time limit of `fuzz` on every generated input.
+const DAYS: &str = "1-15,17-18,20-25";
+
+#[test]
+#[ignore = "runs every implementation on generated inputs, which takes minutes"]
+fn generated() {
+    // Inputs that fail are written to `data/` in the working directory.
+    let dir = std::env::temp_dir().join(format!("aoc2022-fuzz-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2022"))
+        .args(["fuzz", "5", "--days", DAYS])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+}
@@ -1,93 +1,177 @@
 // We should test each solution against the corresponding data in each profile.
-// The question is if we should have a unique test for each solution,
-// or if some tests should run multiple solutions.
-//
-// The profiles are dynamic, so I guess those can be dynamic.
-// The tests will have to be per-solution.
+// The solutions under test are the ones chosen in `aoc2022::registry`,
+// with a test per day like `inputs::day13` that reports every mismatch across all profiles.
+// Additionally, all implementations of a day should agree with each other,
+// regardless of whether the expected answers are known, tested by `inputs::cross_check::day13`.
+// Days without an input in a profile are skipped, while malformed inputs are failures.
+// Like in the runner, implementations are skipped on inputs that break their assumptions,
+// testing the next implementation of the day instead.
 
 mod inputs {
-    macro_rules! input {
-        ($name:ident, $submod:ident) => {
-            #[test]
-            fn $name() {
-                // TODO: move directory finding to util?
-                let dir = std::fs::read_dir("data").expect("no data directory");
-                let mut profiles: Vec<_> = dir.map(|x| x.unwrap()).collect();
-                profiles.sort_by_key(|profile| profile.path());
-                for entry in profiles {
-                    let profile_name = entry
-                        .path()
-                        .file_name()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .to_owned();
-                    let mut input_path = entry.path().to_path_buf();
-                    input_path.push("inputs");
-                    input_path.push(stringify!($name).to_owned() + ".txt");
-
-                    let mut expected_path = entry.path().to_path_buf();
-                    expected_path.push("solutions");
-                    expected_path.push(stringify!($name).to_owned() + ".txt");
-
-                    let input =
-                        std::fs::read_to_string(input_path).expect("couldn't read input file");
-                    let (actual1, actual2) = aoc2022::$submod::$name::run(input.as_str());
-
-                    let expected = std::fs::read_to_string(expected_path).ok();
-                    if let Some((expected1, expected2)) =
-                        expected.as_ref().and_then(|x| x.split_once("\n\n"))
+    use std::collections::HashSet;
+
+    use aoc2022::answer::Answer;
+    use aoc2022::formats;
+    use aoc2022::profile::Profile;
+    use aoc2022::registry::{self, Solution, Visitor};
+
+    /// Run a solution on the input of a profile, if there is one.
+    fn solve<S: Solution>(profile: &Profile, failures: &mut Vec<String>) -> Option<[Answer; 2]> {
+        let input = profile.input(S::DAY).ok()?;
+        match S::try_run(input.as_str()) {
+            Ok((actual1, actual2)) => Some([actual1.into(), actual2.into()]),
+            Err(err) => {
+                let context = format!("{} in {}", S::NAME, profile.name);
+                failures.push(format!("malformed input for {context}: {err}"));
+                None
+            }
+        }
+    }
+
+    /// Whether an implementation supports the input of a profile, or the input is missing or
+    /// malformed, which [`solve`] reports.
+    fn supports<S: Solution>(profile: &Profile) -> bool {
+        let Ok(input) = profile.input(S::DAY) else {
+            return true;
+        };
+        formats::check(S::DAY, &input).is_err() || S::validate(&input).is_empty()
+    }
+
+    struct InputTest {
+        day: usize,
+        profiles: Vec<Profile>,
+        /// Days and profiles for which an implementation has been tested.
+        tested: HashSet<(usize, String)>,
+        failures: Vec<String>,
+    }
+
+    impl Visitor for InputTest {
+        fn visit<S: Solution>(&mut self) {
+            if S::DAY != self.day {
+                return;
+            }
+            for profile in &self.profiles {
+                let tested = (S::DAY, profile.name.clone());
+                if self.tested.contains(&tested) || !supports::<S>(profile) {
+                    continue;
+                }
+                self.tested.insert(tested);
+                let Some(actual) = solve::<S>(profile, &mut self.failures) else {
+                    continue;
+                };
+
+                let expected = profile.expected(S::DAY).unwrap_or_default();
+                for (part, actual) in actual.iter().enumerate() {
+                    if expected.check(part, actual) == Some(false) {
+                        self.failures.push(format!(
+                            "wrong result in {} in {} part {}\nexpected: {}\n  actual: {}",
+                            S::NAME,
+                            profile.name,
+                            part + 1,
+                            expected.0[part].as_ref().unwrap(),
+                            actual.to_string().trim_end(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Answers of an implementation for each profile.
+    type Answers = Vec<Option<[Answer; 2]>>;
+
+    /// Runs every implementation and compares it with the first one of the same day.
+    struct CrossCheck {
+        day: usize,
+        profiles: Vec<Profile>,
+        reference: Option<(usize, &'static str, Answers)>,
+        failures: Vec<String>,
+    }
+
+    impl Visitor for CrossCheck {
+        fn visit<S: Solution>(&mut self) {
+            if S::DAY != self.day {
+                return;
+            }
+            let answers: Answers = self
+                .profiles
+                .iter()
+                .map(|profile| {
+                    supports::<S>(profile).then(|| solve::<S>(profile, &mut self.failures))?
+                })
+                .collect();
+
+            match &self.reference {
+                Some((day, name, expected)) if *day == S::DAY => {
+                    for (profile, (actual, expected)) in
+                        self.profiles.iter().zip(answers.iter().zip(expected))
                     {
-                        custom_assert(
-                            &(profile_name.clone() + " part 1"),
-                            &actual1.to_string(),
-                            expected1,
-                        );
-                        custom_assert(&(profile_name + " part 2"), &actual2.to_string(), expected2);
-                    } else if let Some(expected1) = expected {
-                        custom_assert(
-                            &(profile_name + " part 1"),
-                            &actual1.to_string(),
-                            &expected1,
-                        );
+                        let (Some(actual), Some(expected)) = (actual, expected) else {
+                            continue;
+                        };
+                        for part in 0..2 {
+                            let context = format!(
+                                "{} in {} part {} (compared to {name})",
+                                S::NAME,
+                                profile.name,
+                                part + 1
+                            );
+                            check(&mut self.failures, &context, &actual[part], &expected[part]);
+                        }
                     }
                 }
+                _ => self.reference = Some((S::DAY, S::NAME, answers)),
             }
-        };
+        }
+    }
+
+    fn check(failures: &mut Vec<String>, context: &str, actual: &Answer, expected: &Answer) {
+        if !actual.agrees(expected) {
+            failures.push(format!(
+                "wrong result in {context}\nexpected: {expected}\n  actual: {actual}"
+            ));
+        }
+    }
+
+    fn test_day(day: usize) {
+        let profiles = Profile::all().expect("no data directory");
+        let mut test = InputTest { day, profiles, tested: HashSet::new(), failures: Vec::new() };
+        registry::visit_all(&mut test);
+        assert!(test.failures.is_empty(), "{}", test.failures.join("\n\n"));
     }
 
-    fn custom_assert(profile: &str, actual: &str, expected: &str) {
-        let actual = actual.trim_end();
-        let expected = expected.trim_end();
-        assert!(
-            actual == expected,
-            "wrong result in {profile}\nexpected: {expected}\n  actual: {actual}"
-        );
+    fn cross_check_day(day: usize) {
+        let profiles = Profile::all().expect("no data directory");
+        let mut test = CrossCheck { day, profiles, reference: None, failures: Vec::new() };
+        registry::visit_all(&mut test);
+        assert!(test.failures.is_empty(), "{}", test.failures.join("\n\n"));
     }
 
-    input!(day01, optimized);
-    input!(day02, solutions);
-    input!(day03, solutions);
-    input!(day04, solutions);
-    input!(day05, solutions);
-    input!(day06, optimized);
-    input!(day07, solutions);
-    input!(day08, solutions);
-    input!(day09, solutions);
-    input!(day10, solutions);
-    input!(day11, optimized);
-    input!(day12, solutions);
-    input!(day13, optimized);
-    input!(day14, optimized);
-    input!(day15, optimized);
-    input!(day16, optimized);
-    input!(day17, solutions);
-    input!(day18, solutions);
-    input!(day19, optimized);
-    input!(day20, solutions);
-    input!(day21, solutions);
-    input!(day22, solutions);
-    input!(day23, optimized);
-    input!(day24, optimized);
-    input!(day25, solutions);
+    macro_rules! days {
+        ($( $name:ident = $day:literal ),+ $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    test_day($day);
+                }
+            )+
+
+            mod cross_check {
+                $(
+                    #[test]
+                    fn $name() {
+                        super::cross_check_day($day);
+                    }
+                )+
+            }
+        };
+    }
+
+    days! {
+        day01 = 1, day02 = 2, day03 = 3, day04 = 4, day05 = 5,
+        day06 = 6, day07 = 7, day08 = 8, day09 = 9, day10 = 10,
+        day11 = 11, day12 = 12, day13 = 13, day14 = 14, day15 = 15,
+        day16 = 16, day17 = 17, day18 = 18, day19 = 19, day20 = 20,
+        day21 = 21, day22 = 22, day23 = 23, day24 = 24, day25 = 25,
+    }
 }